@@ -434,6 +434,10 @@ mod tests {
             Ok(None)
         }
 
+        fn latest_sealed_header(&self) -> ProviderResult<Option<SealedHeader>> {
+            Ok(None)
+        }
+
         fn sealed_headers_while(
             &self,
             _range: impl RangeBounds<BlockNumber>,
@@ -1,6 +1,6 @@
 use reth_primitives::{
-    Address, BlockHash, BlockHashOrNumber, BlockNumber, GotExpected, StaticFileSegment,
-    TxHashOrNumber, TxNumber, B256, U256,
+    Address, BlockHash, BlockHashOrNumber, BlockNumber, GotExpected, PruneSegment,
+    StaticFileSegment, TxHashOrNumber, TxNumber, B256, U256,
 };
 use std::path::PathBuf;
 use thiserror::Error;
@@ -23,6 +23,9 @@ pub enum ProviderError {
     /// Error when recovering the sender for a transaction
     #[error("failed to recover sender for transaction")]
     SenderRecoveryError,
+    /// Error when recovering the sender for the transaction with the given number.
+    #[error("failed to recover sender for transaction #{0}")]
+    TransactionSenderRecoveryError(TxNumber),
     /// The header number was not found for the given block hash.
     #[error("block hash {0} does not exist in Headers table")]
     BlockHashNotFound(BlockHash),
@@ -122,6 +125,34 @@ pub enum ProviderError {
     /// Trying to insert data from an unexpected block number.
     #[error("trying to append data to {0} as block #{1} but expected block #{2}")]
     UnexpectedStaticFileBlockNumber(StaticFileSegment, BlockNumber, BlockNumber),
+    /// Trying to remove a block that is not the tip of the chain.
+    #[error("unable to remove block #{0} that is not the tip block #{1}")]
+    NotTipBlock(BlockNumber, BlockNumber),
+    /// Trying to insert a block at a height that is already occupied by a different block.
+    #[error("block #{block_number} is already occupied by {existing}, unable to insert {hash}")]
+    BlockNumberOccupied {
+        /// The block number.
+        block_number: BlockNumber,
+        /// The hash of the block already stored at this height.
+        existing: B256,
+        /// The hash of the block that was attempted to be inserted.
+        hash: B256,
+    },
+    /// Trying to append a receipt whose transaction number doesn't follow the last stored one.
+    #[error("unexpected receipt number: {0}")]
+    UnexpectedReceiptNumber(GotExpected<TxNumber>),
+    /// The stored genesis hash doesn't match the genesis hash of the chain spec.
+    #[error("genesis hash mismatch: {0}")]
+    GenesisHashMismatch(GotExpected<B256>),
+    /// The hash of the stored genesis header doesn't match the genesis hash of the chain spec.
+    #[error("genesis header hash mismatch: {0}")]
+    GenesisHeaderMismatch(GotExpected<B256>),
+    /// The receipts of the block are not available because they were pruned.
+    #[error("receipts of block #{0} are pruned")]
+    BlockReceiptsPruned(BlockNumber),
+    /// Pruning of the segment is not supported by the provider.
+    #[error("pruning {0} segment is not supported")]
+    UnsupportedPruneSegment(PruneSegment),
     /// Error encountered when the block number conversion from U256 to u64 causes an overflow.
     #[error("failed to convert block number U256 to u64: {0}")]
     BlockNumberOverflow(U256),
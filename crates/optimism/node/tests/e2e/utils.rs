@@ -34,18 +34,94 @@ pub(crate) async fn advance_chain(
 
 /// Helper function to create a new eth payload attributes
 pub(crate) fn optimism_payload_attributes(timestamp: u64) -> OptimismPayloadBuilderAttributes {
-    let attributes = PayloadAttributes {
-        timestamp,
-        prev_randao: B256::ZERO,
-        suggested_fee_recipient: Address::ZERO,
-        withdrawals: Some(vec![]),
-        parent_beacon_block_root: Some(B256::ZERO),
-    };
-
-    OptimismPayloadBuilderAttributes {
-        payload_attributes: EthPayloadBuilderAttributes::new(B256::ZERO, attributes),
-        transactions: vec![],
-        no_tx_pool: false,
-        gas_limit: Some(30_000_000),
+    OpPayloadAttributesBuilder::new(timestamp).build()
+}
+
+/// Builder for [`OptimismPayloadBuilderAttributes`], defaulting to the values used by
+/// [`optimism_payload_attributes`].
+#[derive(Debug, Clone)]
+pub(crate) struct OpPayloadAttributesBuilder {
+    timestamp: u64,
+    prev_randao: B256,
+    suggested_fee_recipient: Address,
+    gas_limit: Option<u64>,
+    no_tx_pool: bool,
+}
+
+impl OpPayloadAttributesBuilder {
+    /// Creates a new builder for payload attributes with the given timestamp
+    pub(crate) fn new(timestamp: u64) -> Self {
+        Self {
+            timestamp,
+            prev_randao: B256::ZERO,
+            suggested_fee_recipient: Address::ZERO,
+            gas_limit: Some(30_000_000),
+            no_tx_pool: false,
+        }
+    }
+
+    /// Sets the timestamp
+    pub(crate) fn timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the `prev_randao`
+    pub(crate) fn prev_randao(mut self, prev_randao: B256) -> Self {
+        self.prev_randao = prev_randao;
+        self
+    }
+
+    /// Sets the fee recipient
+    pub(crate) fn suggested_fee_recipient(mut self, suggested_fee_recipient: Address) -> Self {
+        self.suggested_fee_recipient = suggested_fee_recipient;
+        self
     }
+
+    /// Sets the gas limit
+    pub(crate) fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Sets whether transactions from the pool are excluded
+    pub(crate) fn no_tx_pool(mut self, no_tx_pool: bool) -> Self {
+        self.no_tx_pool = no_tx_pool;
+        self
+    }
+
+    /// Builds the payload attributes
+    pub(crate) fn build(self) -> OptimismPayloadBuilderAttributes {
+        let attributes = PayloadAttributes {
+            timestamp: self.timestamp,
+            prev_randao: self.prev_randao,
+            suggested_fee_recipient: self.suggested_fee_recipient,
+            withdrawals: Some(vec![]),
+            parent_beacon_block_root: Some(B256::ZERO),
+        };
+
+        OptimismPayloadBuilderAttributes {
+            payload_attributes: EthPayloadBuilderAttributes::new(B256::ZERO, attributes),
+            transactions: vec![],
+            no_tx_pool: self.no_tx_pool,
+            gas_limit: self.gas_limit,
+        }
+    }
+}
+
+#[test]
+fn op_payload_attributes_builder() {
+    let attributes = OpPayloadAttributesBuilder::new(1)
+        .timestamp(2)
+        .prev_randao(B256::with_last_byte(1))
+        .suggested_fee_recipient(Address::with_last_byte(1))
+        .gas_limit(100_000)
+        .no_tx_pool(true)
+        .build();
+
+    assert_eq!(attributes.payload_attributes.timestamp, 2);
+    assert_eq!(attributes.payload_attributes.prev_randao, B256::with_last_byte(1));
+    assert_eq!(attributes.payload_attributes.suggested_fee_recipient, Address::with_last_byte(1));
+    assert_eq!(attributes.gas_limit, Some(100_000));
+    assert!(attributes.no_tx_pool);
 }
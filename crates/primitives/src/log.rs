@@ -18,8 +18,30 @@ where
     bloom
 }
 
+/// Calculate block logs bloom from the blooms of its receipts.
+pub fn block_bloom<'a>(receipt_blooms: impl IntoIterator<Item = &'a Bloom>) -> Bloom {
+    receipt_blooms.into_iter().fold(Bloom::ZERO, |bloom, receipt_bloom| bloom | *receipt_bloom)
+}
+
+/// Assign block-global log indices to the logs of a block's receipts.
+///
+/// Returns the index of each log, grouped by receipt. Indices are counted sequentially across all
+/// receipts, so receipts without logs don't contribute any.
+pub fn assign_log_indices<'a>(receipts: impl IntoIterator<Item = &'a [Log]>) -> Vec<Vec<u64>> {
+    let mut next_index = 0;
+    receipts
+        .into_iter()
+        .map(|logs| {
+            let indices = (next_index..next_index + logs.len() as u64).collect();
+            next_index += logs.len() as u64;
+            indices
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{assign_log_indices, block_bloom, logs_bloom};
     use alloy_primitives::{Address, Bytes, Log as AlloyLog, B256};
     use alloy_rlp::{RlpDecodable, RlpEncodable};
     use proptest::proptest;
@@ -78,4 +100,39 @@ mod tests {
             assert_eq!(compacted_log, compacted_alloy_log);
         }
     }
+
+    #[test]
+    fn block_bloom_from_receipt_blooms() {
+        let a = vec![AlloyLog::new_unchecked(
+            Address::with_last_byte(1),
+            vec![B256::with_last_byte(1)],
+            Bytes::new(),
+        )];
+        let b = vec![
+            AlloyLog::new_unchecked(Address::with_last_byte(2), vec![], Bytes::new()),
+            AlloyLog::new_unchecked(
+                Address::with_last_byte(3),
+                vec![B256::with_last_byte(2), B256::with_last_byte(3)],
+                Bytes::new(),
+            ),
+        ];
+
+        assert_eq!(
+            block_bloom([&logs_bloom(&a), &logs_bloom(&b)]),
+            logs_bloom(a.iter().chain(b.iter()))
+        );
+        assert_eq!(block_bloom([]), logs_bloom([]));
+    }
+
+    #[test]
+    fn assign_log_indices_across_receipts() {
+        let log = AlloyLog::new_unchecked(Address::ZERO, vec![], Bytes::new());
+        let receipts = [vec![log.clone(), log.clone()], vec![], vec![log.clone()], vec![]];
+
+        assert_eq!(
+            assign_log_indices(receipts.iter().map(Vec::as_slice)),
+            vec![vec![0, 1], vec![], vec![2], vec![]]
+        );
+        assert!(assign_log_indices(std::iter::empty()).is_empty());
+    }
 }
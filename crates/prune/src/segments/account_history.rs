@@ -1,7 +1,5 @@
 use crate::{
-    segments::{
-        history::prune_history_indices, PruneInput, PruneOutput, PruneOutputCheckpoint, Segment,
-    },
+    segments::{PruneInput, PruneOutput, PruneOutputCheckpoint, Segment},
     PrunerError,
 };
 use reth_db::{database::Database, models::ShardedKey, tables};
@@ -78,12 +76,12 @@ impl<DB: Database> Segment<DB> for AccountHistory {
             .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
             .unwrap_or(range_end);
 
-        let (processed, pruned_indices) = prune_history_indices::<DB, tables::AccountsHistory, _>(
-            provider,
-            last_changeset_pruned_block,
-            |a, b| a.key == b.key,
-            |key| ShardedKey::last(key.key),
-        )?;
+        let (processed, pruned_indices) = provider
+            .prune_history_indices::<tables::AccountsHistory, _>(
+                last_changeset_pruned_block,
+                |a, b| a.key == b.key,
+                |key| ShardedKey::last(key.key),
+            )?;
         trace!(target: "pruner", %processed, pruned = %pruned_indices, %done, "Pruned account history (history)");
 
         let progress = PruneProgress::new(done, &limiter);
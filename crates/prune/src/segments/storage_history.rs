@@ -1,7 +1,5 @@
 use crate::{
-    segments::{
-        history::prune_history_indices, PruneInput, PruneOutput, PruneOutputCheckpoint, Segment,
-    },
+    segments::{PruneInput, PruneOutput, PruneOutputCheckpoint, Segment},
     PrunerError,
 };
 use reth_db::{
@@ -82,12 +80,12 @@ impl<DB: Database> Segment<DB> for StorageHistory {
             .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
             .unwrap_or(range_end);
 
-        let (processed, pruned_indices) = prune_history_indices::<DB, tables::StoragesHistory, _>(
-            provider,
-            last_changeset_pruned_block,
-            |a, b| a.address == b.address && a.sharded_key.key == b.sharded_key.key,
-            |key| StorageShardedKey::last(key.address, key.sharded_key.key),
-        )?;
+        let (processed, pruned_indices) = provider
+            .prune_history_indices::<tables::StoragesHistory, _>(
+                last_changeset_pruned_block,
+                |a, b| a.address == b.address && a.sharded_key.key == b.sharded_key.key,
+                |key| StorageShardedKey::last(key.address, key.sharded_key.key),
+            )?;
         trace!(target: "pruner", %processed, deleted = %pruned_indices, %done, "Pruned storage history (history)");
 
         let progress = PruneProgress::new(done, &limiter);
@@ -425,7 +425,7 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
         // Unwind account and storage changesets, as well as receipts.
         //
         // This also updates `PlainStorageState` and `PlainAccountState`.
-        let bundle_state_with_receipts = provider.unwind_or_peek_state::<true>(range.clone())?;
+        let bundle_state_with_receipts = provider.unwind_state(range.clone())?;
 
         // Construct a `ExExNotification` if we have ExEx's installed.
         if self.exex_manager_handle.has_exexs() {
@@ -453,7 +453,7 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
             // files do not support filters.
             //
             // If we hit this case, the receipts have already been unwound by the call to
-            // `unwind_or_peek_state`.
+            // `unwind_state`.
         }
 
         // Update the checkpoint.
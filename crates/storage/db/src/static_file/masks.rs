@@ -11,6 +11,7 @@ use reth_primitives::{BlockHash, Header};
 add_static_file_mask!(HeaderMask, Header, 0b001);
 add_static_file_mask!(HeaderMask, <HeaderTerminalDifficulties as Table>::Value, 0b010);
 add_static_file_mask!(HeaderMask, BlockHash, 0b100);
+add_static_file_mask!(HeaderMask, Header, <HeaderTerminalDifficulties as Table>::Value, 0b011);
 add_static_file_mask!(HeaderMask, Header, BlockHash, 0b101);
 add_static_file_mask!(HeaderMask, <HeaderTerminalDifficulties as Table>::Value, BlockHash, 0b110);
 
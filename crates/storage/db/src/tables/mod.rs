@@ -25,7 +25,7 @@ pub(crate) mod utils;
 
 use crate::{
     abstraction::table::Table,
-    table::DupSort,
+    table::{Decode, DupSort, Encode},
     tables::{
         codecs::CompactU256,
         models::{
@@ -36,6 +36,7 @@ use crate::{
             ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
         },
     },
+    DatabaseError,
 };
 use reth_primitives::{
     stage::StageCheckpoint,
@@ -43,6 +44,7 @@ use reth_primitives::{
     Account, Address, BlockHash, BlockNumber, Bytecode, Header, IntegerList, PruneCheckpoint,
     PruneSegment, Receipt, StorageEntry, TransactionSignedNoHash, TxHash, TxNumber, B256,
 };
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Enum for the types of tables present in libmdbx.
@@ -377,6 +379,35 @@ tables! {
 
     /// Stores the history of client versions that have accessed the database with write privileges by unix timestamp in seconds.
     table VersionHistory<Key = u64, Value = ClientVersion>;
+
+    /// Stores generic chain state info, like the last finalized block.
+    table ChainState<Key = ChainStateKey, Value = BlockNumber>;
+}
+
+/// Keys for the [`ChainState`] table.
+#[derive(Ord, Clone, Eq, PartialOrd, PartialEq, Debug, Deserialize, Serialize, Hash)]
+pub enum ChainStateKey {
+    /// Last finalized block key.
+    LastFinalizedBlock,
+}
+
+impl Encode for ChainStateKey {
+    type Encoded = [u8; 1];
+
+    fn encode(self) -> Self::Encoded {
+        match self {
+            Self::LastFinalizedBlock => [0],
+        }
+    }
+}
+
+impl Decode for ChainStateKey {
+    fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        match value.as_ref() {
+            [0] => Ok(Self::LastFinalizedBlock),
+            _ => Err(DatabaseError::Decode),
+        }
+    }
 }
 
 // Alias types.
@@ -0,0 +1,185 @@
+use crate::{BlockHashReader, DatabaseProvider, HeaderProvider};
+use parking_lot::Mutex;
+use reth_db::transaction::{DbTx, DbTxMut};
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{BlockHash, BlockNumber, Header, SealedHeader, B256, U256};
+use schnellru::{ByLength, LruMap};
+use std::{fmt, ops::RangeBounds};
+
+/// Default number of entries kept by [`CachedHeaderProvider`], matching the window of blocks
+/// accessible through the `BLOCKHASH` opcode.
+pub const DEFAULT_HEADER_CACHE_SIZE: u32 = 256;
+
+/// A [`DatabaseProvider`] wrapper that memoizes [`HeaderProvider::header_by_number`] and
+/// [`BlockHashReader::block_hash`] lookups in bounded LRU caches.
+///
+/// This is useful for re-execution workloads (e.g. tracing) that repeatedly read the same recent
+/// headers. All other lookups are delegated to the inner provider. The inner provider is not
+/// exposed by reference, so that every write goes through [`CachedHeaderProvider::write`], which
+/// invalidates the caches.
+pub struct CachedHeaderProvider<TX> {
+    /// Inner database provider.
+    provider: DatabaseProvider<TX>,
+    /// Cached headers by block number.
+    headers: Mutex<LruMap<BlockNumber, Header, ByLength>>,
+    /// Cached canonical hashes by block number.
+    hashes: Mutex<LruMap<BlockNumber, B256, ByLength>>,
+}
+
+impl<TX> CachedHeaderProvider<TX> {
+    /// Creates a new cached provider with [`DEFAULT_HEADER_CACHE_SIZE`] entries per cache.
+    pub fn new(provider: DatabaseProvider<TX>) -> Self {
+        Self::with_capacity(provider, DEFAULT_HEADER_CACHE_SIZE)
+    }
+
+    /// Creates a new cached provider that keeps at most `capacity` entries per cache.
+    pub fn with_capacity(provider: DatabaseProvider<TX>, capacity: u32) -> Self {
+        Self {
+            provider,
+            headers: Mutex::new(LruMap::new(ByLength::new(capacity))),
+            hashes: Mutex::new(LruMap::new(ByLength::new(capacity))),
+        }
+    }
+
+    /// Consumes the wrapper and returns the inner provider.
+    pub fn into_inner(self) -> DatabaseProvider<TX> {
+        self.provider
+    }
+
+    /// Clears all cached entries.
+    pub fn clear_cache(&self) {
+        self.headers.lock().clear();
+        self.hashes.lock().clear();
+    }
+}
+
+impl<TX: DbTxMut> CachedHeaderProvider<TX> {
+    /// Runs a write operation against the inner provider and invalidates the caches afterwards.
+    pub fn write<R>(&self, f: impl FnOnce(&DatabaseProvider<TX>) -> R) -> R {
+        let result = f(&self.provider);
+        self.clear_cache();
+        result
+    }
+}
+
+impl<TX> fmt::Debug for CachedHeaderProvider<TX>
+where
+    DatabaseProvider<TX>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedHeaderProvider")
+            .field("provider", &self.provider)
+            .field("cached_headers", &self.headers.lock().len())
+            .field("cached_hashes", &self.hashes.lock().len())
+            .finish()
+    }
+}
+
+impl<TX: DbTx> HeaderProvider for CachedHeaderProvider<TX> {
+    fn header(&self, block_hash: &BlockHash) -> ProviderResult<Option<Header>> {
+        self.provider.header(block_hash)
+    }
+
+    fn header_by_number(&self, num: BlockNumber) -> ProviderResult<Option<Header>> {
+        if let Some(header) = self.headers.lock().get(&num) {
+            return Ok(Some(header.clone()))
+        }
+
+        let header = self.provider.header_by_number(num)?;
+        if let Some(header) = &header {
+            self.headers.lock().insert(num, header.clone());
+        }
+        Ok(header)
+    }
+
+    fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>> {
+        self.provider.header_td(hash)
+    }
+
+    fn header_td_by_number(&self, number: BlockNumber) -> ProviderResult<Option<U256>> {
+        self.provider.header_td_by_number(number)
+    }
+
+    fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> ProviderResult<Vec<Header>> {
+        self.provider.headers_range(range)
+    }
+
+    fn sealed_header(&self, number: BlockNumber) -> ProviderResult<Option<SealedHeader>> {
+        self.provider.sealed_header(number)
+    }
+
+    fn latest_sealed_header(&self) -> ProviderResult<Option<SealedHeader>> {
+        self.provider.latest_sealed_header()
+    }
+
+    fn sealed_headers_while(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        predicate: impl FnMut(&SealedHeader) -> bool,
+    ) -> ProviderResult<Vec<SealedHeader>> {
+        self.provider.sealed_headers_while(range, predicate)
+    }
+}
+
+impl<TX: DbTx> BlockHashReader for CachedHeaderProvider<TX> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        if let Some(hash) = self.hashes.lock().get(&number) {
+            return Ok(Some(*hash))
+        }
+
+        let hash = self.provider.block_hash(number)?;
+        if let Some(hash) = hash {
+            self.hashes.lock().insert(number, hash);
+        }
+        Ok(hash)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.provider.canonical_hashes_range(start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CachedHeaderProvider;
+    use crate::{test_utils::create_test_provider_factory, BlockHashReader, HeaderProvider};
+    use reth_db::{tables, transaction::DbTxMut};
+    use reth_primitives::{Header, B256};
+
+    #[test]
+    fn invalidates_on_write() {
+        let factory = create_test_provider_factory();
+        let provider = CachedHeaderProvider::with_capacity(factory.provider_rw().unwrap().0, 2);
+
+        let header = Header { number: 1, gas_limit: 1, ..Default::default() };
+        provider.write(|provider| {
+            provider.tx_ref().put::<tables::Headers>(1, header.clone()).unwrap();
+            provider.tx_ref().put::<tables::CanonicalHeaders>(1, B256::with_last_byte(1)).unwrap();
+        });
+        assert_eq!(provider.header_by_number(1).unwrap(), Some(header.clone()));
+        assert_eq!(provider.block_hash(1).unwrap(), Some(B256::with_last_byte(1)));
+        assert_eq!(provider.headers.lock().len(), 1);
+        assert_eq!(provider.hashes.lock().len(), 1);
+
+        // Writes through the wrapper invalidate the caches.
+        let updated = Header { gas_limit: 2, ..header };
+        provider.write(|provider| {
+            provider.tx_ref().put::<tables::Headers>(1, updated.clone()).unwrap();
+            provider.tx_ref().put::<tables::CanonicalHeaders>(1, B256::ZERO).unwrap();
+        });
+        assert_eq!(provider.headers.lock().len(), 0);
+        assert_eq!(provider.hashes.lock().len(), 0);
+        assert_eq!(provider.header_by_number(1).unwrap(), Some(updated));
+        assert_eq!(provider.block_hash(1).unwrap(), Some(B256::ZERO));
+
+        // Missing entries are not cached.
+        assert_eq!(provider.header_by_number(2).unwrap(), None);
+        assert_eq!(provider.block_hash(2).unwrap(), None);
+        assert_eq!(provider.headers.lock().len(), 1);
+        assert_eq!(provider.hashes.lock().len(), 1);
+    }
+}
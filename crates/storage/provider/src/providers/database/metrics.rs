@@ -4,30 +4,46 @@ use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 pub(crate) struct DurationsRecorder {
-    start: Instant,
+    /// Start of the recording, or `None` if the recorder is disabled.
+    start: Option<Instant>,
     pub(crate) actions: Vec<(Action, Duration)>,
     latest: Option<Duration>,
 }
 
 impl Default for DurationsRecorder {
     fn default() -> Self {
-        Self { start: Instant::now(), actions: Vec::new(), latest: None }
+        Self::new(true)
     }
 }
 
 impl DurationsRecorder {
+    /// Creates a new recorder. If `enabled` is `false`, all recording methods are no-ops and no
+    /// time measurements are taken.
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self { start: enabled.then(Instant::now), actions: Vec::new(), latest: None }
+    }
+
+    /// Returns `true` if the recorder is enabled.
+    pub(crate) const fn is_enabled(&self) -> bool {
+        self.start.is_some()
+    }
+
     /// Saves the provided duration for future logging and instantly reports as a metric with
     /// `action` label.
     pub(crate) fn record_duration(&mut self, action: Action, duration: Duration) {
+        let Some(start) = self.start else { return };
+
         self.actions.push((action, duration));
         Metrics::new_with_labels(&[("action", action.as_str())]).duration.record(duration);
-        self.latest = Some(self.start.elapsed());
+        self.latest = Some(start.elapsed());
     }
 
     /// Records the duration since last record, saves it for future logging and instantly reports as
     /// a metric with `action` label.
     pub(crate) fn record_relative(&mut self, action: Action) {
-        let elapsed = self.start.elapsed();
+        let Some(start) = self.start else { return };
+
+        let elapsed = start.elapsed();
         let duration = elapsed - self.latest.unwrap_or_default();
 
         self.actions.push((action, duration));
@@ -4,9 +4,10 @@ use crate::{
     traits::{BlockSource, ReceiptProvider},
     BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
     EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HeaderSyncMode,
-    ProviderError, PruneCheckpointReader, StageCheckpointReader, StateProviderBox,
+    ProviderError, PruneCheckpointReader, StageCheckpointReader, StateProviderBox, StorageReader,
     TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
+use rayon::prelude::*;
 use reth_db::{database::Database, init_db, models::StoredBlockBodyIndices, DatabaseEnv};
 use reth_evm::ConfigureEvmEnv;
 use reth_interfaces::{provider::ProviderResult, RethError, RethResult};
@@ -14,8 +15,8 @@ use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, ChainInfo,
     ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, StaticFileSegment, TransactionMeta, TransactionSigned, TransactionSignedNoHash,
-    TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    SealedHeader, StaticFileSegment, StorageEntry, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
@@ -25,9 +26,11 @@ use std::{
 };
 use tracing::trace;
 
+mod cached;
 mod metrics;
 mod provider;
 
+pub use cached::{CachedHeaderProvider, DEFAULT_HEADER_CACHE_SIZE};
 pub use provider::{DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW};
 use reth_db::mdbx::DatabaseArguments;
 
@@ -124,6 +127,21 @@ impl<DB: Database> ProviderFactory<DB> {
         )))
     }
 
+    /// Opens a read-write provider, runs `f` with it and commits the transaction if `f` returns
+    /// `Ok`. Otherwise, all writes are discarded.
+    ///
+    /// See [`DatabaseProviderRW::commit_with`].
+    #[track_caller]
+    pub fn with_provider_rw<T, E>(
+        &self,
+        f: impl FnOnce(&DatabaseProviderRW<DB>) -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        E: From<ProviderError>,
+    {
+        self.provider_rw()?.commit_with(f)
+    }
+
     /// State provider for latest block
     #[track_caller]
     pub fn latest(&self) -> ProviderResult<StateProviderBox> {
@@ -153,6 +171,36 @@ impl<DB: Database> ProviderFactory<DB> {
         trace!(target: "providers::db", ?block_number, %block_hash, "Returning historical state provider for block hash");
         Ok(state_provider)
     }
+
+    /// Get plainstate storages for addresses and storage keys, reading them in parallel.
+    ///
+    /// Addresses are partitioned across rayon workers. Database transactions can't be shared
+    /// between threads, so every worker opens its own read-only provider, which requires the
+    /// database to allow as many concurrent read transactions as there are workers. Transactions
+    /// are opened independently, so concurrent writes may be observed by some workers only.
+    ///
+    /// The output order matches the input order, same as
+    /// [`StorageReader::plain_state_storages`].
+    pub fn plain_state_storages_par(
+        &self,
+        addresses_with_keys: Vec<(Address, Vec<B256>)>,
+    ) -> ProviderResult<Vec<(Address, Vec<StorageEntry>)>> {
+        if addresses_with_keys.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let chunk_size = addresses_with_keys.len().div_ceil(rayon::current_num_threads());
+        let chunks = addresses_with_keys
+            .par_chunks(chunk_size)
+            .map(|chunk| {
+                self.provider()?.plain_state_storages(
+                    chunk.iter().map(|(address, keys)| (*address, keys.iter().copied())),
+                )
+            })
+            .collect::<ProviderResult<Vec<_>>>()?;
+
+        Ok(chunks.into_iter().flatten().collect())
+    }
 }
 
 impl<DB: Database> DatabaseProviderFactory<DB> for ProviderFactory<DB> {
@@ -223,6 +271,10 @@ impl<DB: Database> HeaderProvider for ProviderFactory<DB> {
         )
     }
 
+    fn latest_sealed_header(&self) -> ProviderResult<Option<SealedHeader>> {
+        self.provider()?.latest_sealed_header()
+    }
+
     fn sealed_headers_range(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -313,6 +365,13 @@ impl<DB: Database> BlockReader for ProviderFactory<DB> {
         self.provider()?.ommers(id)
     }
 
+    fn ommers_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Header>)>> {
+        self.provider()?.ommers_range(range)
+    }
+
     fn block_body_indices(
         &self,
         number: BlockNumber,
@@ -320,6 +379,13 @@ impl<DB: Database> BlockReader for ProviderFactory<DB> {
         self.provider()?.block_body_indices(number)
     }
 
+    fn block_body_indices_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, StoredBlockBodyIndices)>> {
+        self.provider()?.block_body_indices_range(range)
+    }
+
     fn block_with_senders(
         &self,
         id: BlockHashOrNumber,
@@ -328,6 +394,14 @@ impl<DB: Database> BlockReader for ProviderFactory<DB> {
         self.provider()?.block_with_senders(id, transaction_kind)
     }
 
+    fn sealed_block_with_senders(
+        &self,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        self.provider()?.sealed_block_with_senders(id, transaction_kind)
+    }
+
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
         self.provider()?.block_range(range)
     }
@@ -366,6 +440,13 @@ impl<DB: Database> TransactionsProvider for ProviderFactory<DB> {
         )
     }
 
+    fn transactions_by_ids(
+        &self,
+        ids: impl IntoIterator<Item = TxNumber>,
+    ) -> ProviderResult<Vec<Option<TransactionSignedNoHash>>> {
+        self.provider()?.transactions_by_ids(ids)
+    }
+
     fn transaction_by_hash(&self, hash: TxHash) -> ProviderResult<Option<TransactionSigned>> {
         self.provider()?.transaction_by_hash(hash)
     }
@@ -444,6 +525,10 @@ impl<DB: Database> ReceiptProvider for ProviderFactory<DB> {
             |_| true,
         )
     }
+
+    fn total_receipts_count(&self) -> ProviderResult<u64> {
+        self.provider()?.total_receipts_count()
+    }
 }
 
 impl<DB: Database> WithdrawalsProvider for ProviderFactory<DB> {
@@ -468,6 +553,10 @@ impl<DB: Database> StageCheckpointReader for ProviderFactory<DB> {
     fn get_stage_checkpoint_progress(&self, id: StageId) -> ProviderResult<Option<Vec<u8>>> {
         self.provider()?.get_stage_checkpoint_progress(id)
     }
+
+    fn get_all_stage_checkpoints(&self) -> ProviderResult<Vec<(StageId, StageCheckpoint)>> {
+        self.provider()?.get_all_stage_checkpoints()
+    }
 }
 
 impl<DB: Database> EvmEnvProvider for ProviderFactory<DB> {
@@ -554,36 +643,62 @@ impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
     ) -> ProviderResult<Option<PruneCheckpoint>> {
         self.provider()?.get_prune_checkpoint(segment)
     }
+
+    fn get_all_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
+        self.provider()?.get_all_prune_checkpoints()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::ProviderFactory;
     use crate::{
-        providers::StaticFileWriter, test_utils::create_test_provider_factory, BlockHashReader,
-        BlockNumReader, BlockWriter, HeaderSyncGapProvider, HeaderSyncMode, TransactionsProvider,
+        providers::StaticFileWriter, test_utils::create_test_provider_factory, AccountExtReader,
+        BlockHashReader, BlockNumReader, BlockReader, BlockSource, BlockWriter,
+        BundleStateWithReceipts, ChainSpecProvider, HashingWriter, HeaderProvider,
+        HeaderSyncGapProvider, HeaderSyncMode, PruneCheckpointReader, PruneCheckpointWriter,
+        ReceiptProvider, StageCheckpointReader, StageCheckpointWriter, StorageReader,
+        TransactionVariant, TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
     };
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
     use rand::Rng;
     use reth_db::{
         mdbx::DatabaseArguments,
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
+            ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
+        },
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
+        transaction::{DbTx, DbTxMut},
+        BlockNumberList,
     };
     use reth_interfaces::{
         provider::ProviderError,
         test_utils::{
             generators,
-            generators::{random_block, random_header},
+            generators::{random_block, random_header, random_receipt},
         },
         RethError,
     };
     use reth_primitives::{
-        hex_literal::hex, ChainSpecBuilder, PruneMode, PruneModes, SealedBlock, StaticFileSegment,
-        TxNumber, B256, U256,
+        block_bloom,
+        constants::EMPTY_ROOT_HASH,
+        hex_literal::hex,
+        keccak256,
+        proofs::{self, calculate_withdrawals_root, storage_root_unhashed},
+        stage::{StageCheckpoint, StageId},
+        Account, Address, BlockNumber, Bloom, ChainSpecBuilder, GotExpected, Header,
+        PruneCheckpoint, PruneLimiter, PruneMode, PruneModes, PruneSegment, Receipt, SealedBlock,
+        SealedBlockWithSenders, StaticFileSegment, StorageEntry, TransactionSignedEcRecovered,
+        TxNumber, Withdrawal, Withdrawals, B256, SEPOLIA, U256,
+    };
+    use std::{
+        collections::BTreeMap,
+        ops::{Bound, RangeInclusive},
+        sync::Arc,
     };
-    use std::{ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
 
     #[test]
@@ -631,6 +746,32 @@ mod tests {
         provider.block_hash(0).unwrap();
     }
 
+    #[test]
+    fn with_provider_rw_commits_on_ok() {
+        let factory = create_test_provider_factory();
+        let checkpoint = StageCheckpoint::new(1);
+
+        let result: Result<(), ProviderError> = factory.with_provider_rw(|provider| {
+            provider.save_stage_checkpoint(StageId::Headers, checkpoint)?;
+            Err(ProviderError::UnsupportedProvider)
+        });
+        assert_matches!(result, Err(ProviderError::UnsupportedProvider));
+        assert_matches!(
+            factory.provider().unwrap().get_stage_checkpoint(StageId::Headers),
+            Ok(None)
+        );
+
+        factory
+            .with_provider_rw(|provider| {
+                provider.save_stage_checkpoint(StageId::Headers, checkpoint)
+            })
+            .unwrap();
+        assert_matches!(
+            factory.provider().unwrap().get_stage_checkpoint(StageId::Headers),
+            Ok(Some(stored)) if stored == checkpoint
+        );
+    }
+
     #[test]
     fn insert_block_with_prune_modes() {
         let factory = create_test_provider_factory();
@@ -669,6 +810,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn insert_block_with_prune_window() {
+        let mut rng = generators::rng();
+
+        // (prune mode, highest known block, whether the inserted block 5 is pruned)
+        let cases = [
+            (PruneMode::Full, None, true),
+            (PruneMode::Before(10), None, true),
+            (PruneMode::Before(5), None, false),
+            (PruneMode::Distance(3), Some(10), true),
+            (PruneMode::Distance(3), Some(7), false),
+            (PruneMode::Distance(3), None, false),
+        ];
+
+        for (prune_mode, tip, pruned) in cases {
+            let factory = create_test_provider_factory();
+            let provider = factory.provider_rw().unwrap();
+            if let Some(tip) = tip {
+                provider.tx_ref().put::<tables::CanonicalHeaders>(tip, B256::random()).unwrap();
+            }
+
+            let block = random_block(&mut rng, 5, None, Some(1), Some(0));
+            let hash = block.body[0].hash();
+            assert_matches!(
+                provider.insert_block(
+                    block.try_seal_with_senders().unwrap(),
+                    Some(&PruneModes {
+                        sender_recovery: Some(prune_mode),
+                        transaction_lookup: Some(prune_mode),
+                        ..PruneModes::none()
+                    })
+                ),
+                Ok(_)
+            );
+
+            assert_eq!(
+                provider.tx_ref().get::<tables::TransactionSenders>(0).unwrap().is_none(),
+                pruned,
+                "{prune_mode:?}"
+            );
+            assert_eq!(
+                provider.tx_ref().get::<tables::TransactionHashNumbers>(hash).unwrap().is_none(),
+                pruned,
+                "{prune_mode:?}"
+            );
+        }
+    }
+
     #[test]
     fn get_take_block_transaction_range_recover_senders() {
         let factory = create_test_provider_factory();
@@ -712,32 +901,2336 @@ mod tests {
     }
 
     #[test]
-    fn header_sync_gap_lookup() {
+    fn sealed_block_with_senders() {
         let factory = create_test_provider_factory();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), Some(0));
+
         let provider = factory.provider_rw().unwrap();
+        assert_matches!(
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None),
+            Ok(_)
+        );
+
+        let sealed = provider
+            .sealed_block_with_senders(0.into(), TransactionVariant::WithHash)
+            .unwrap()
+            .expect("block exists");
+        assert_eq!(sealed.hash(), block.hash());
+        assert_eq!(sealed.block.body, block.body);
+        assert_eq!(Some(sealed.senders), block.senders());
+
+        assert_matches!(
+            provider.sealed_block_with_senders(1.into(), TransactionVariant::WithHash),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn find_sealed_block_by_hash() {
+        let factory = create_test_provider_factory();
 
         let mut rng = generators::rng();
-        let consensus_tip = rng.gen();
-        let (_tip_tx, tip_rx) = watch::channel(consensus_tip);
-        let mode = HeaderSyncMode::Tip(tip_rx);
+        let block = random_block(&mut rng, 0, None, Some(3), Some(0));
 
-        // Genesis
-        let checkpoint = 0;
-        let head = random_header(&mut rng, 0, None);
+        let provider = factory.provider_rw().unwrap();
+        assert_matches!(
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None),
+            Ok(_)
+        );
 
-        // Empty database
+        for source in [BlockSource::Any, BlockSource::Database] {
+            let sealed = provider
+                .find_sealed_block_by_hash(block.hash(), source)
+                .unwrap()
+                .expect("block exists");
+            assert_eq!(sealed.hash(), block.hash());
+            assert_eq!(sealed.body, block.body);
+        }
         assert_matches!(
-            provider.sync_gap(mode.clone(), checkpoint),
-            Err(RethError::Provider(ProviderError::HeaderNotFound(block_number)))
-                if block_number.as_number().unwrap() == checkpoint
+            provider.find_sealed_block_by_hash(block.hash(), BlockSource::Pending),
+            Ok(None)
         );
+        assert_matches!(provider.find_sealed_block_by_hash(B256::ZERO, BlockSource::Any), Ok(None));
+    }
 
-        // Checkpoint and no gap
-        let mut static_file_writer =
-            provider.static_file_provider().latest_writer(StaticFileSegment::Headers).unwrap();
-        static_file_writer.append_header(head.header().clone(), U256::ZERO, head.hash()).unwrap();
-        static_file_writer.commit().unwrap();
-        drop(static_file_writer);
+    #[test]
+    fn headers_with_td_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, None, Some(0), Some(0));
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        let expected = provider
+            .headers_range(0..=2)
+            .unwrap()
+            .into_iter()
+            .map(|header| {
+                let td = provider.header_td_by_number(header.number).unwrap().unwrap();
+                (header, td)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(provider.headers_with_td_range(0..=2).unwrap(), expected);
+        assert_eq!(provider.headers_with_td_range(1..=5).unwrap(), expected[1..]);
+        assert_eq!(provider.headers_with_td_range(1..).unwrap(), expected[1..]);
+    }
+
+    #[test]
+    fn tx_range_for_block_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        for (number, tx_count) in [(0, 2), (1, 0), (2, 1)] {
+            let block = random_block(&mut rng, number, None, Some(tx_count), Some(0));
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        assert_eq!(provider.tx_range_for_block_range(0..=0).unwrap(), Some(0..2));
+        assert_eq!(provider.tx_range_for_block_range(1..=1).unwrap(), None);
+        assert_eq!(provider.tx_range_for_block_range(1..=2).unwrap(), Some(2..3));
+        assert_eq!(provider.tx_range_for_block_range(0..=2).unwrap(), Some(0..3));
+        assert_matches!(
+            provider.tx_range_for_block_range(2..=3),
+            Err(ProviderError::BlockBodyIndicesNotFound(3))
+        );
+    }
+
+    #[test]
+    fn tx_count_in_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        for (number, tx_count) in [(0, 2), (1, 0), (2, 1)] {
+            let block = random_block(&mut rng, number, None, Some(tx_count), Some(0));
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        assert_eq!(provider.tx_count_in_range(0..=2).unwrap(), 3);
+        assert_eq!(provider.tx_count_in_range(1..=1).unwrap(), 0);
+        assert_eq!(provider.tx_count_in_range(1..=3).unwrap(), 1);
+        assert_eq!(provider.tx_count_in_range(4..=5).unwrap(), 0);
+    }
+
+    #[test]
+    fn prune_segment() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let (address, storage_key) = (Address::with_last_byte(1), B256::with_last_byte(1));
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, None, Some(2), Some(0));
+            for (tx_num, transaction) in (number * 2..).zip(block.body.iter()) {
+                let receipt = random_receipt(&mut rng, transaction, Some(0));
+                provider.tx_ref().put::<tables::Receipts>(tx_num, receipt).unwrap();
+            }
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+
+            provider
+                .tx_ref()
+                .put::<tables::AccountChangeSets>(number, AccountBeforeTx { address, info: None })
+                .unwrap();
+            provider
+                .tx_ref()
+                .put::<tables::StorageChangeSets>(
+                    BlockNumberAddress((number, address)),
+                    StorageEntry { key: storage_key, value: U256::ZERO },
+                )
+                .unwrap();
+        }
+        provider
+            .tx_ref()
+            .put::<tables::AccountsHistory>(
+                ShardedKey::last(address),
+                BlockNumberList::new([0, 1, 2]).unwrap(),
+            )
+            .unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::StoragesHistory>(
+                StorageShardedKey::last(address, storage_key),
+                BlockNumberList::new([0, 1, 2]).unwrap(),
+            )
+            .unwrap();
+
+        let prune_mode = PruneMode::Before(2);
+        for (segment, expected_pruned) in [
+            (PruneSegment::SenderRecovery, 4),
+            (PruneSegment::TransactionLookup, 4),
+            (PruneSegment::Receipts, 4),
+            (PruneSegment::Transactions, 4),
+            (PruneSegment::AccountHistory, 2),
+            (PruneSegment::StorageHistory, 2),
+        ] {
+            assert_eq!(
+                provider
+                    .prune_segment(segment, 1, prune_mode, &mut PruneLimiter::default())
+                    .unwrap(),
+                (expected_pruned, true),
+                "{segment}"
+            );
+            assert_eq!(
+                provider.get_prune_checkpoint(segment).unwrap().and_then(|c| c.block_number),
+                Some(1),
+                "{segment}"
+            );
+
+            // Everything up to the checkpoint is already pruned
+            assert_eq!(
+                provider
+                    .prune_segment(segment, 1, prune_mode, &mut PruneLimiter::default())
+                    .unwrap(),
+                (0, true),
+                "{segment}"
+            );
+        }
+
+        assert_eq!(provider.table::<tables::TransactionSenders>().unwrap().len(), 2);
+        assert_eq!(provider.table::<tables::TransactionHashNumbers>().unwrap().len(), 2);
+        assert_eq!(provider.table::<tables::Receipts>().unwrap().len(), 2);
+        assert_eq!(provider.table::<tables::Transactions>().unwrap().len(), 2);
+        assert_eq!(provider.table::<tables::AccountChangeSets>().unwrap().len(), 1);
+        assert_eq!(provider.table::<tables::StorageChangeSets>().unwrap().len(), 1);
+        assert_eq!(
+            provider
+                .tx_ref()
+                .get::<tables::AccountsHistory>(ShardedKey::last(address))
+                .unwrap()
+                .map(|list| list.iter().collect::<Vec<_>>()),
+            Some(vec![2])
+        );
+        assert_eq!(
+            provider
+                .tx_ref()
+                .get::<tables::StoragesHistory>(StorageShardedKey::last(address, storage_key))
+                .unwrap()
+                .map(|list| list.iter().collect::<Vec<_>>()),
+            Some(vec![2])
+        );
+
+        assert_matches!(
+            provider.prune_segment(
+                PruneSegment::Headers,
+                1,
+                prune_mode,
+                &mut PruneLimiter::default()
+            ),
+            Err(ProviderError::UnsupportedPruneSegment(PruneSegment::Headers))
+        );
+    }
+
+    #[test]
+    fn changed_storage_slots_for_address() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let (first, second) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        for (block_number, address, slot) in
+            [(0, first, 1), (0, first, 2), (0, second, 3), (1, second, 4), (2, first, 5)]
+        {
+            provider
+                .tx_ref()
+                .put::<tables::StorageChangeSets>(
+                    BlockNumberAddress((block_number, address)),
+                    StorageEntry { key: B256::with_last_byte(slot), value: U256::ZERO },
+                )
+                .unwrap();
+        }
+
+        let slots = |address, range| {
+            provider
+                .changed_storage_slots_for_address(address, range)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            slots(first, 0..=2),
+            vec![B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(5)]
+        );
+        assert_eq!(slots(first, 1..=2), vec![B256::with_last_byte(5)]);
+        assert_eq!(slots(second, 0..=2), vec![B256::with_last_byte(3), B256::with_last_byte(4)]);
+        assert!(slots(Address::with_last_byte(3), 0..=2).is_empty());
+    }
+
+    #[test]
+    fn append_history_index_limited() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let (first, second) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let index_updates = BTreeMap::from([(first, vec![1, 2]), (second, vec![3])]);
+
+        let mut limiter = PruneLimiter::default().set_deleted_entries_limit(1);
+        let remaining = provider
+            .append_history_index_limited::<_, tables::AccountsHistory>(
+                index_updates,
+                ShardedKey::new,
+                &mut limiter,
+            )
+            .unwrap();
+        assert_eq!(remaining, BTreeMap::from([(second, vec![3])]));
+
+        let remaining = provider
+            .append_history_index_limited::<_, tables::AccountsHistory>(
+                remaining,
+                ShardedKey::new,
+                &mut PruneLimiter::default(),
+            )
+            .unwrap();
+        assert!(remaining.is_empty());
+
+        let history = provider.table::<tables::AccountsHistory>().unwrap();
+        assert_eq!(
+            history
+                .into_iter()
+                .map(|(key, list)| (key.key, list.iter().collect::<Vec<_>>()))
+                .collect::<Vec<_>>(),
+            vec![(first, vec![1, 2]), (second, vec![3])]
+        );
+    }
+
+    #[test]
+    fn ommers_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut expected = Vec::new();
+        for (number, ommers_count) in [(0, 0), (1, 2), (2, 0)] {
+            let block = random_block(&mut rng, number, None, Some(0), Some(ommers_count));
+            expected.push((number, block.ommers.clone()));
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        assert_eq!(provider.ommers_range(0..=2).unwrap(), expected);
+        assert_eq!(provider.ommers_range(1..=5).unwrap(), expected[1..]);
+        assert!(provider.ommers_range(3..=5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn senders_by_tx_range_recovered() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), Some(0));
+        let senders = block.senders().unwrap();
+        assert_matches!(provider.insert_block(block.try_seal_with_senders().unwrap(), None), Ok(_));
+
+        // Remove one of the stored senders, as if it was pruned
+        provider.tx_ref().delete::<tables::TransactionSenders>(1, None).unwrap();
+        assert_eq!(provider.senders_by_tx_range(0..3).unwrap().len(), 2);
+
+        assert_eq!(provider.senders_by_tx_range_recovered(0..3).unwrap(), senders);
+        assert_eq!(provider.senders_by_tx_range_recovered(1..5).unwrap(), senders[1..]);
+
+        // Remove the first transaction, as if it was pruned. The senders of the remaining
+        // transactions must not be shifted onto the pruned one.
+        provider.tx_ref().delete::<tables::Transactions>(0, None).unwrap();
+        assert_eq!(provider.senders_by_tx_range_recovered(0..3).unwrap(), senders[1..]);
+        assert_eq!(provider.senders_by_tx_range_recovered(..).unwrap(), senders[1..]);
+    }
+
+    #[test]
+    fn transactions_with_senders_by_block() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), Some(0));
+        let expected = block
+            .body
+            .iter()
+            .cloned()
+            .zip(block.senders().unwrap())
+            .map(|(tx, sender)| TransactionSignedEcRecovered::from_signed_transaction(tx, sender))
+            .collect::<Vec<_>>();
+        let empty_block = random_block(&mut rng, 1, Some(block.hash()), Some(0), Some(0));
+        assert_matches!(provider.insert_block(block.try_seal_with_senders().unwrap(), None), Ok(_));
+        assert_matches!(
+            provider.insert_block(empty_block.clone().try_seal_with_senders().unwrap(), None),
+            Ok(_)
+        );
+
+        // Remove one of the stored senders, as if it was pruned
+        provider.tx_ref().delete::<tables::TransactionSenders>(1, None).unwrap();
+
+        assert_eq!(
+            provider.transactions_with_senders_by_block(0u64.into()).unwrap(),
+            Some(expected)
+        );
+        assert_eq!(
+            provider.transactions_with_senders_by_block(empty_block.hash().into()).unwrap(),
+            Some(Vec::new())
+        );
+        assert_eq!(provider.transactions_with_senders_by_block(2u64.into()).unwrap(), None);
+    }
+
+    #[test]
+    fn with_chain_spec() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap().0;
+
+        let genesis_header = factory.chain_spec().sealed_genesis_header();
+        provider.tx_ref().put::<tables::CanonicalHeaders>(0, genesis_header.hash()).unwrap();
+        provider.tx_ref().put::<tables::Headers>(0, genesis_header.clone().unseal()).unwrap();
+        assert_eq!(provider.validate_genesis(), Ok(()));
+
+        let provider = provider.with_chain_spec(SEPOLIA.clone());
+        assert_eq!(
+            provider.validate_genesis(),
+            Err(ProviderError::GenesisHashMismatch(GotExpected {
+                got: genesis_header.hash(),
+                expected: SEPOLIA.genesis_hash()
+            }))
+        );
+    }
+
+    #[test]
+    fn headers_while() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let headers = (0..5)
+            .map(|number| Header { number, timestamp: number * 12, ..Default::default() })
+            .collect::<Vec<_>>();
+        for header in &headers {
+            provider.tx_ref().put::<tables::Headers>(header.number, header.clone()).unwrap();
+        }
+
+        assert_eq!(
+            provider.headers_while(1..5, |header| header.timestamp < 36).unwrap(),
+            headers[1..3]
+        );
+        assert_eq!(provider.headers_while(0..5, |_| true).unwrap(), headers);
+        assert!(provider.headers_while(0..5, |_| false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn transaction_blocks_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        // Block 0 has transactions 0 and 1, block 1 is empty and block 2 has transactions 2 to 4
+        provider.tx_ref().put::<tables::TransactionBlocks>(1, 0).unwrap();
+        provider.tx_ref().put::<tables::TransactionBlocks>(4, 2).unwrap();
+
+        assert_eq!(
+            provider.transaction_blocks_range(..).unwrap(),
+            vec![(0, 0), (1, 0), (2, 2), (3, 2), (4, 2)]
+        );
+        // Ranges starting and ending in the middle of a block
+        assert_eq!(provider.transaction_blocks_range(1..4).unwrap(), vec![(1, 0), (2, 2), (3, 2)]);
+        assert_eq!(provider.transaction_blocks_range(3..=3).unwrap(), vec![(3, 2)]);
+        // Transactions after the last known block are not returned
+        assert_eq!(provider.transaction_blocks_range(4..10).unwrap(), vec![(4, 2)]);
+        assert!(provider.transaction_blocks_range(5..10).unwrap().is_empty());
+        assert!(provider.transaction_blocks_range(2..2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn history_shards() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let (address, other_address) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let (storage_key, other_storage_key) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        for (key, blocks) in [
+            (ShardedKey::new(address, 2), vec![1, 2]),
+            (ShardedKey::last(address), vec![3]),
+            (ShardedKey::last(other_address), vec![4]),
+        ] {
+            provider
+                .tx_ref()
+                .put::<tables::AccountsHistory>(key, BlockNumberList::new(blocks).unwrap())
+                .unwrap();
+        }
+        for (key, blocks) in [
+            (StorageShardedKey::last(address, storage_key), vec![1, 3]),
+            (StorageShardedKey::last(address, other_storage_key), vec![2]),
+        ] {
+            provider
+                .tx_ref()
+                .put::<tables::StoragesHistory>(key, BlockNumberList::new(blocks).unwrap())
+                .unwrap();
+        }
+
+        assert_eq!(
+            provider.account_history_shards(address).unwrap(),
+            vec![(2, vec![1, 2]), (u64::MAX, vec![3])]
+        );
+        assert!(provider.account_history_shards(Address::ZERO).unwrap().is_empty());
+        assert_eq!(
+            provider.storage_history_shards(address, storage_key).unwrap(),
+            vec![(u64::MAX, vec![1, 3])]
+        );
+        assert!(provider.storage_history_shards(other_address, storage_key).unwrap().is_empty());
+    }
+
+    #[test]
+    fn validate_genesis() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let genesis_header = factory.chain_spec().genesis_header();
+        let genesis_hash = factory.chain_spec().genesis_hash();
+
+        assert_eq!(provider.validate_genesis(), Err(ProviderError::HeaderNotFound(0u64.into())));
+
+        provider.tx_ref().put::<tables::CanonicalHeaders>(0, genesis_hash).unwrap();
+        provider.tx_ref().put::<tables::Headers>(0, genesis_header.clone()).unwrap();
+        assert_eq!(provider.validate_genesis(), Ok(()));
+
+        // Genesis hash of another chain
+        provider.tx_ref().put::<tables::CanonicalHeaders>(0, B256::ZERO).unwrap();
+        assert_eq!(
+            provider.validate_genesis(),
+            Err(ProviderError::GenesisHashMismatch(GotExpected {
+                got: B256::ZERO,
+                expected: genesis_hash
+            }))
+        );
+
+        // Corrupted genesis header
+        let header = Header { gas_limit: genesis_header.gas_limit + 1, ..genesis_header };
+        provider.tx_ref().put::<tables::CanonicalHeaders>(0, genesis_hash).unwrap();
+        provider.tx_ref().put::<tables::Headers>(0, header.clone()).unwrap();
+        assert_eq!(
+            provider.validate_genesis(),
+            Err(ProviderError::GenesisHeaderMismatch(GotExpected {
+                got: header.hash_slow(),
+                expected: genesis_hash
+            }))
+        );
+    }
+
+    #[test]
+    fn earliest_available_block() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        assert_eq!(provider.earliest_available_block().unwrap(), 0);
+
+        for (segment, block_number) in [
+            (PruneSegment::Receipts, Some(5)),
+            (PruneSegment::AccountHistory, Some(10)),
+            (PruneSegment::StorageHistory, None),
+        ] {
+            provider
+                .save_prune_checkpoint(
+                    segment,
+                    PruneCheckpoint { block_number, tx_number: None, prune_mode: PruneMode::Full },
+                )
+                .unwrap();
+        }
+        assert_eq!(provider.earliest_available_block().unwrap(), 11);
+    }
+
+    #[test]
+    fn insert_block_without_metrics() {
+        let factory = create_test_provider_factory();
+        let mut provider = factory.provider_rw().unwrap();
+        provider.set_insert_block_metrics(false);
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), Some(0));
+        assert_matches!(
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None),
+            Ok(_)
+        );
+
+        assert_eq!(
+            provider.block_body_indices(0).unwrap(),
+            Some(StoredBlockBodyIndices { first_tx_num: 0, tx_count: 3 })
+        );
+        assert_eq!(provider.transactions_by_block(0.into()).unwrap(), Some(block.body));
+    }
+
+    #[test]
+    fn transaction_by_block_and_index() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut blocks = Vec::new();
+        for number in 0..2 {
+            let block = random_block(&mut rng, number, None, Some(2), Some(0));
+            blocks.push(block.clone());
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        for block in &blocks {
+            for (index, transaction) in block.body.iter().enumerate() {
+                assert_eq!(
+                    provider.transaction_by_block_and_index(block.number.into(), index as u64),
+                    Ok(Some(transaction.clone()))
+                );
+                assert_eq!(
+                    provider.transaction_by_block_and_index(block.hash().into(), index as u64),
+                    Ok(Some(transaction.clone()))
+                );
+            }
+        }
+
+        // Out of range index and unknown block
+        assert_eq!(provider.transaction_by_block_and_index(0u64.into(), 2), Ok(None));
+        assert_eq!(provider.transaction_by_block_and_index(2u64.into(), 0), Ok(None));
+    }
+
+    #[test]
+    fn receipt_by_block_and_index() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), Some(0));
+        let receipts = block
+            .body
+            .iter()
+            .map(|transaction| random_receipt(&mut rng, transaction, Some(1)))
+            .collect::<Vec<_>>();
+        let block_hash = block.hash();
+        assert_matches!(provider.insert_block(block.try_seal_with_senders().unwrap(), None), Ok(_));
+
+        // The receipt of the last transaction is missing, as if it was pruned
+        for (tx_num, receipt) in receipts.iter().take(2).enumerate() {
+            provider.tx_ref().put::<tables::Receipts>(tx_num as TxNumber, receipt.clone()).unwrap();
+        }
+
+        assert_eq!(
+            provider.receipt_by_block_and_index(0u64.into(), 0),
+            Ok(Some(receipts[0].clone()))
+        );
+        assert_eq!(
+            provider.receipt_by_block_and_index(block_hash.into(), 1),
+            Ok(Some(receipts[1].clone()))
+        );
+        assert_eq!(provider.receipt_by_block_and_index(0u64.into(), 2), Ok(None));
+
+        // Out of range index and unknown block
+        assert_eq!(provider.receipt_by_block_and_index(0u64.into(), 3), Ok(None));
+        assert_eq!(provider.receipt_by_block_and_index(1u64.into(), 0), Ok(None));
+    }
+
+    #[test]
+    fn receipts_root_by_block() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(2), Some(0));
+        let receipts = block
+            .body
+            .iter()
+            .map(|transaction| random_receipt(&mut rng, transaction, Some(1)))
+            .collect::<Vec<_>>();
+        let empty_block = random_block(&mut rng, 1, Some(block.hash()), Some(0), Some(0));
+        let pruned_block = random_block(&mut rng, 2, Some(empty_block.hash()), Some(1), Some(0));
+        for block in [block, empty_block, pruned_block] {
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+        for (tx_num, receipt) in receipts.iter().enumerate() {
+            provider.tx_ref().put::<tables::Receipts>(tx_num as TxNumber, receipt.clone()).unwrap();
+        }
+
+        #[cfg(not(feature = "optimism"))]
+        assert_eq!(
+            provider.receipts_root_by_block(0u64.into()),
+            Ok(Some(proofs::calculate_receipt_root_ref(&receipts.iter().collect::<Vec<_>>())))
+        );
+        assert_eq!(provider.receipts_root_by_block(1u64.into()), Ok(Some(EMPTY_ROOT_HASH)));
+        assert_eq!(
+            provider.receipts_root_by_block(2u64.into()),
+            Err(ProviderError::BlockReceiptsPruned(2))
+        );
+        assert_eq!(provider.receipts_root_by_block(3u64.into()), Ok(None));
+    }
+
+    #[test]
+    fn transactions_for_tracing() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut expected = Vec::new();
+        for (number, tx_count) in [(0, 2), (1, 0), (2, 3)] {
+            let block = random_block(&mut rng, number, None, Some(tx_count), Some(0));
+            let transactions = block
+                .body
+                .iter()
+                .cloned()
+                .zip(block.senders().unwrap())
+                .map(|(tx, sender)| {
+                    TransactionSignedEcRecovered::from_signed_transaction(tx, sender)
+                })
+                .collect::<Vec<_>>();
+            expected.push((number, block.header.clone().unseal(), transactions));
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        // Remove one of the stored senders, as if it was pruned
+        provider.tx_ref().delete::<tables::TransactionSenders>(3, None).unwrap();
+
+        assert_eq!(provider.transactions_for_tracing(0..=2).unwrap(), expected);
+        assert_eq!(provider.transactions_for_tracing(1..=2).unwrap(), expected[1..]);
+        assert!(provider.transactions_for_tracing(3..5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn block_with_senders_range_pruned_senders() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut senders = Vec::new();
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, None, Some(2), Some(0));
+            senders.push(block.senders().unwrap());
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        // Remove stored senders across multiple blocks, as if they were pruned
+        for tx_num in [0, 3, 4] {
+            provider.tx_ref().delete::<tables::TransactionSenders>(tx_num, None).unwrap();
+        }
+
+        let blocks = provider.block_with_senders_range(0..=2).unwrap();
+        assert_eq!(blocks.iter().map(|block| block.senders.clone()).collect::<Vec<_>>(), senders);
+        assert_eq!(
+            blocks.into_iter().map(|block| block.block).collect::<Vec<_>>(),
+            provider.block_range(0..=2).unwrap()
+        );
+    }
+
+    #[test]
+    fn block_with_senders_pruned_senders() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), Some(0));
+        let senders = block.senders().unwrap();
+        assert_matches!(provider.insert_block(block.try_seal_with_senders().unwrap(), None), Ok(_));
+
+        // All senders are stored
+        for block_with_senders in [
+            provider.block_with_senders(0u64.into(), TransactionVariant::WithHash),
+            provider.block_with_stored_senders(0u64.into(), TransactionVariant::WithHash),
+        ] {
+            assert_matches!(block_with_senders, Ok(Some(block)) if block.senders == senders);
+        }
+
+        // Remove one of the stored senders, as if it was pruned
+        provider.tx_ref().delete::<tables::TransactionSenders>(1, None).unwrap();
+
+        assert_matches!(
+            provider.block_with_senders(0u64.into(), TransactionVariant::WithHash),
+            Ok(Some(block)) if block.senders == senders
+        );
+        assert_matches!(
+            provider.sealed_block_with_senders(0u64.into(), TransactionVariant::NoHash),
+            Ok(Some(block)) if block.senders == senders
+        );
+        assert_matches!(
+            provider.block_with_stored_senders(0u64.into(), TransactionVariant::WithHash),
+            Err(ProviderError::MismatchOfTransactionAndSenderId { tx_id: 1 })
+        );
+    }
+
+    #[test]
+    fn transactions_by_hashes_with_meta() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut hashes = Vec::new();
+        for number in 0..2 {
+            let block = random_block(&mut rng, number, None, Some(2), Some(0));
+            hashes.extend(block.body.iter().map(|tx| tx.hash()));
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        let requested = [hashes[3], B256::random(), hashes[0], hashes[2]];
+        let expected = requested
+            .iter()
+            .map(|hash| provider.transaction_by_hash_with_meta(*hash).unwrap())
+            .collect::<Vec<_>>();
+        assert!(expected[1].is_none());
+        assert_eq!(expected.iter().filter(|result| result.is_some()).count(), 3);
+        assert_eq!(provider.transactions_by_hashes_with_meta(requested).unwrap(), expected);
+    }
+
+    #[test]
+    fn transaction_senders_by_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), Some(0));
+        let senders = block.senders().unwrap();
+        assert_matches!(provider.insert_block(block.try_seal_with_senders().unwrap(), None), Ok(_));
+
+        let mut recovered = provider.transaction_senders_by_range(0..3).unwrap();
+        recovered.sort_by_key(|(_, tx_num)| *tx_num);
+        assert_eq!(recovered, senders.into_iter().zip(0u64..).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn get_all_prune_checkpoints() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        assert!(provider.get_all_prune_checkpoints().unwrap().is_empty());
+
+        let checkpoints = vec![
+            (
+                PruneSegment::SenderRecovery,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: Some(2),
+                    prune_mode: PruneMode::Full,
+                },
+            ),
+            (
+                PruneSegment::Receipts,
+                PruneCheckpoint {
+                    block_number: Some(3),
+                    tx_number: None,
+                    prune_mode: PruneMode::Distance(64),
+                },
+            ),
+        ];
+        for (segment, checkpoint) in &checkpoints {
+            provider.save_prune_checkpoint(*segment, *checkpoint).unwrap();
+        }
+
+        let mut all = provider.get_all_prune_checkpoints().unwrap();
+        all.sort_by_key(|(segment, _)| *segment);
+        let mut expected = checkpoints;
+        expected.sort_by_key(|(segment, _)| *segment);
+        assert_eq!(all, expected);
+    }
+
+    #[test]
+    fn get_all_stage_checkpoints() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        assert!(provider.get_all_stage_checkpoints().unwrap().is_empty());
+
+        let checkpoints = vec![
+            (StageId::Headers, StageCheckpoint::new(1)),
+            (StageId::Execution, StageCheckpoint::new(2)),
+        ];
+        for (id, checkpoint) in &checkpoints {
+            provider.save_stage_checkpoint(*id, *checkpoint).unwrap();
+        }
+        // Custom stages are not returned
+        provider.save_stage_checkpoint(StageId::Other("Custom"), StageCheckpoint::new(3)).unwrap();
+
+        let mut all = provider.get_all_stage_checkpoints().unwrap();
+        all.sort_by_key(|(id, _)| id.as_str().to_string());
+        let mut expected = checkpoints;
+        expected.sort_by_key(|(id, _)| id.as_str().to_string());
+        assert_eq!(all, expected);
+    }
+
+    #[test]
+    fn for_each_account_and_storage_change() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let (first, second) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        for (block_number, address) in [(1, second), (1, first), (2, first)] {
+            provider
+                .tx_ref()
+                .put::<tables::AccountChangeSets>(
+                    block_number,
+                    AccountBeforeTx { address, info: None },
+                )
+                .unwrap();
+            provider
+                .tx_ref()
+                .put::<tables::StorageChangeSets>(
+                    BlockNumberAddress((block_number, address)),
+                    StorageEntry {
+                        key: B256::with_last_byte(block_number as u8),
+                        value: U256::ZERO,
+                    },
+                )
+                .unwrap();
+        }
+
+        let mut account_changes = Vec::new();
+        provider
+            .for_each_account_change(1..=2, |block_number, account_before| {
+                account_changes.push((block_number, account_before.address))
+            })
+            .unwrap();
+        assert_eq!(account_changes, vec![(1, first), (1, second), (2, first)]);
+
+        let mut account_changes = Vec::new();
+        provider
+            .for_each_account_change((Bound::Excluded(1), Bound::Unbounded), |block_number, _| {
+                account_changes.push(block_number)
+            })
+            .unwrap();
+        assert_eq!(account_changes, vec![2]);
+
+        let mut storage_changes = Vec::new();
+        provider
+            .for_each_storage_change(BlockNumberAddress::range(1..=1), |key, storage_entry| {
+                storage_changes.push((key.block_number(), key.address(), storage_entry.key))
+            })
+            .unwrap();
+        assert_eq!(
+            storage_changes,
+            vec![(1, first, B256::with_last_byte(1)), (1, second, B256::with_last_byte(1))]
+        );
+
+        let mut storage_changes = Vec::new();
+        provider
+            .for_each_storage_change(
+                (Bound::Excluded(BlockNumberAddress((1, first))), Bound::Unbounded),
+                |key, _| storage_changes.push((key.block_number(), key.address())),
+            )
+            .unwrap();
+        assert_eq!(storage_changes, vec![(1, second), (2, first)]);
+    }
+
+    #[test]
+    fn account_diffs_in_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let account = |nonce| Some(Account { nonce, ..Default::default() });
+        let (changed, created, destroyed) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        for (block_number, address, info) in [
+            (1, changed, account(1)),
+            (1, created, None),
+            (2, changed, account(2)),
+            (2, destroyed, account(5)),
+            (3, changed, account(3)),
+        ] {
+            provider
+                .tx_ref()
+                .put::<tables::AccountChangeSets>(block_number, AccountBeforeTx { address, info })
+                .unwrap();
+        }
+        provider.tx_ref().put::<tables::PlainAccountState>(changed, account(4).unwrap()).unwrap();
+        provider.tx_ref().put::<tables::PlainAccountState>(created, account(1).unwrap()).unwrap();
+
+        assert_eq!(
+            provider.account_diffs_in_range(1..=2).unwrap(),
+            BTreeMap::from([
+                (changed, (account(1), account(4))),
+                (created, (None, account(1))),
+                (destroyed, (account(5), None)),
+            ])
+        );
+        assert_eq!(
+            provider.account_diffs_in_range(2..).unwrap(),
+            BTreeMap::from([(changed, (account(2), account(4))), (destroyed, (account(5), None))])
+        );
+    }
+
+    #[test]
+    fn storage_changesets_by_block() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let (first, second) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let entry = |key: u8, value: u64| StorageEntry {
+            key: B256::with_last_byte(key),
+            value: U256::from(value),
+        };
+        for (block_number, address, storage_entry) in [
+            (1, second, entry(1, 10)),
+            (1, first, entry(2, 20)),
+            (1, first, entry(1, 30)),
+            (2, first, entry(1, 40)),
+        ] {
+            provider
+                .tx_ref()
+                .put::<tables::StorageChangeSets>(
+                    BlockNumberAddress((block_number, address)),
+                    storage_entry,
+                )
+                .unwrap();
+        }
+
+        assert_eq!(
+            provider.storage_changesets_by_block(1).unwrap(),
+            vec![(first, entry(1, 30)), (first, entry(2, 20)), (second, entry(1, 10))]
+        );
+        assert_eq!(provider.storage_changesets_by_block(2).unwrap(), vec![(first, entry(1, 40))]);
+        assert!(provider.storage_changesets_by_block(3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn changed_accounts_with_range_bounds() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let address = Address::with_last_byte;
+        for block_number in 1..=3 {
+            provider
+                .tx_ref()
+                .put::<tables::AccountChangeSets>(
+                    block_number,
+                    AccountBeforeTx { address: address(block_number as u8), info: None },
+                )
+                .unwrap();
+        }
+
+        let changed = |range: (Bound<BlockNumber>, Bound<BlockNumber>)| {
+            provider.changed_accounts_with_range(range).unwrap().into_iter().collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            provider.changed_accounts_with_range(1..=2).unwrap().into_iter().collect::<Vec<_>>(),
+            vec![address(1), address(2)]
+        );
+        assert_eq!(
+            provider.changed_accounts_with_range(1..3).unwrap().into_iter().collect::<Vec<_>>(),
+            vec![address(1), address(2)]
+        );
+        assert_eq!(
+            provider.changed_accounts_with_range(..2).unwrap().into_iter().collect::<Vec<_>>(),
+            vec![address(1)]
+        );
+        assert_eq!(
+            provider.changed_accounts_with_range(2..).unwrap().into_iter().collect::<Vec<_>>(),
+            vec![address(2), address(3)]
+        );
+        assert_eq!(
+            provider.changed_accounts_with_range(..).unwrap().into_iter().collect::<Vec<_>>(),
+            vec![address(1), address(2), address(3)]
+        );
+        assert_eq!(changed((Bound::Excluded(1), Bound::Included(3))), vec![address(2), address(3)]);
+        assert_eq!(changed((Bound::Excluded(2), Bound::Unbounded)), vec![address(3)]);
+        assert_eq!(changed((Bound::Excluded(u64::MAX), Bound::Unbounded)), vec![]);
+        assert!(provider.changed_accounts_with_range(4..).unwrap().is_empty());
+    }
+
+    #[test]
+    fn transactions_by_ids() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), Some(0));
+        assert_matches!(provider.insert_block(block.try_seal_with_senders().unwrap(), None), Ok(_));
+
+        let transaction = |id| provider.transaction_by_id_no_hash(id).unwrap();
+        assert_eq!(
+            provider.transactions_by_ids([2, 0, 7, 2]).unwrap(),
+            vec![transaction(2), transaction(0), None, transaction(2)]
+        );
+        assert!(provider.transactions_by_ids(Vec::<TxNumber>::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn plain_state_storages_par() {
+        let factory = create_test_provider_factory();
+
+        let provider = factory.provider_rw().unwrap();
+        let addresses = (0..10).map(Address::with_last_byte).collect::<Vec<_>>();
+        for address in &addresses {
+            for key in 0..3 {
+                provider
+                    .tx_ref()
+                    .put::<tables::PlainStorageState>(
+                        *address,
+                        StorageEntry { key: B256::with_last_byte(key), value: U256::from(key + 1) },
+                    )
+                    .unwrap();
+            }
+        }
+        provider.commit().unwrap();
+
+        // Query addresses in reverse order, including a missing storage key
+        let addresses_with_keys = addresses
+            .iter()
+            .rev()
+            .map(|address| (*address, vec![B256::with_last_byte(2), B256::with_last_byte(5)]))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            factory.plain_state_storages_par(addresses_with_keys.clone()).unwrap(),
+            factory.provider().unwrap().plain_state_storages(addresses_with_keys).unwrap()
+        );
+        assert!(factory.plain_state_storages_par(Vec::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn is_block_canonical() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(0), Some(0));
+        assert!(!provider.is_block_canonical(block.hash()).unwrap());
+
+        assert_matches!(
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None),
+            Ok(_)
+        );
+        assert!(provider.is_block_canonical(block.hash()).unwrap());
+
+        // Header of a reorged out block is still known, but not canonical
+        let reorged_hash: B256 = rng.gen();
+        provider.tx_ref().put::<tables::HeaderNumbers>(reorged_hash, 0).unwrap();
+        assert!(!provider.is_block_canonical(reorged_hash).unwrap());
+    }
+
+    #[test]
+    fn block_numbers() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let blocks = (0..3)
+            .map(|number| random_block(&mut rng, number, None, Some(0), Some(0)))
+            .collect::<Vec<_>>();
+        for block in &blocks {
+            assert_matches!(
+                provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        let unknown: B256 = rng.gen();
+        assert_eq!(
+            provider
+                .block_numbers([blocks[2].hash(), unknown, blocks[0].hash(), blocks[2].hash()])
+                .unwrap(),
+            vec![Some(2), None, Some(0), Some(2)]
+        );
+        assert!(provider.block_numbers([]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn plain_accounts_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let accounts = (0..5)
+            .map(|i| {
+                (Address::with_last_byte(i), Account { nonce: i as u64, ..Default::default() })
+            })
+            .collect::<Vec<_>>();
+        for (address, account) in &accounts {
+            provider.tx_ref().put::<tables::PlainAccountState>(*address, *account).unwrap();
+        }
+
+        let address = Address::with_last_byte;
+        assert_eq!(provider.plain_accounts_range(..).unwrap(), accounts);
+        assert_eq!(provider.plain_accounts_range(address(1)..address(3)).unwrap(), accounts[1..3]);
+        assert_eq!(provider.plain_accounts_range(address(1)..=address(3)).unwrap(), accounts[1..4]);
+        assert_eq!(
+            provider.plain_accounts_range((Bound::Excluded(address(1)), Bound::Unbounded)).unwrap(),
+            accounts[2..]
+        );
+        assert!(provider.plain_accounts_range(address(5)..).unwrap().is_empty());
+    }
+
+    #[test]
+    fn sealed_header_and_td_by_hash() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(0), Some(0));
+        assert_eq!(provider.sealed_header_and_td_by_hash(block.hash()).unwrap(), None);
+
+        assert_matches!(
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None),
+            Ok(_)
+        );
+        assert_eq!(
+            provider.sealed_header_and_td_by_hash(block.hash()).unwrap(),
+            Some((block.header.clone(), block.difficulty))
+        );
+
+        provider.tx_ref().delete::<tables::HeaderTerminalDifficulties>(0, None).unwrap();
+        assert_matches!(
+            provider.sealed_header_and_td_by_hash(block.hash()),
+            Err(ProviderError::HeaderNotFound(_))
+        );
+    }
+
+    #[test]
+    fn latest_header_and_hash() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        assert_eq!(provider.latest_header_and_hash().unwrap(), None);
+
+        let mut rng = generators::rng();
+        for number in 0..2 {
+            let block = random_block(&mut rng, number, None, Some(0), Some(0));
+            assert_matches!(
+                provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+            assert_eq!(
+                provider.latest_header_and_hash().unwrap(),
+                Some((block.header.header().clone(), block.hash()))
+            );
+        }
+    }
+
+    #[test]
+    fn resolved_best_block_number() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, None, Some(0), Some(0));
+            assert_matches!(
+                provider.insert_historical_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+        assert_eq!(provider.best_block_number().unwrap(), 0);
+        assert_eq!(provider.resolved_best_block_number().unwrap(), 2);
+
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(1)).unwrap();
+        assert_eq!(provider.resolved_best_block_number().unwrap(), 1);
+    }
+
+    #[test]
+    fn reset_stage_checkpoint() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        for stage_id in [StageId::Headers, StageId::MerkleExecute] {
+            provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(10)).unwrap();
+            provider.save_stage_checkpoint_progress(stage_id, vec![1, 2, 3]).unwrap();
+        }
+
+        provider.reset_stage_checkpoint(StageId::MerkleExecute).unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::MerkleExecute).unwrap(),
+            Some(StageCheckpoint::default())
+        );
+        assert_eq!(provider.get_stage_checkpoint_progress(StageId::MerkleExecute).unwrap(), None);
+
+        // Other stages are left intact
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::Headers).unwrap(),
+            Some(StageCheckpoint::new(10))
+        );
+        assert_eq!(
+            provider.get_stage_checkpoint_progress(StageId::Headers).unwrap(),
+            Some(vec![1, 2, 3])
+        );
+
+        // Stages without a checkpoint get a zero checkpoint
+        provider.reset_stage_checkpoint(StageId::Bodies).unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::Bodies).unwrap(),
+            Some(StageCheckpoint::default())
+        );
+    }
+
+    #[test]
+    fn block_exists() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut hashes = Vec::new();
+        for number in 0..2 {
+            let block = random_block(&mut rng, number, None, Some(1), Some(0));
+            hashes.push(block.hash());
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+        provider.tx_ref().delete::<tables::BlockBodyIndices>(1, None).unwrap();
+
+        assert!(provider.block_exists(0.into()).unwrap());
+        assert!(provider.block_exists(hashes[0].into()).unwrap());
+        // Header without a body
+        assert!(!provider.block_exists(1.into()).unwrap());
+        assert!(!provider.block_exists(hashes[1].into()).unwrap());
+        // Unknown blocks
+        assert!(!provider.block_exists(2.into()).unwrap());
+        assert!(!provider.block_exists(B256::random().into()).unwrap());
+        // Reorged out block at a canonical height
+        let reorged_hash = B256::random();
+        provider.tx_ref().put::<tables::HeaderNumbers>(reorged_hash, 0).unwrap();
+        assert!(!provider.block_exists(reorged_hash.into()).unwrap());
+    }
+
+    #[test]
+    fn append_blocks_with_state_no_trie() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let blocks = (0..3)
+            .map(|number| {
+                random_block(&mut rng, number, None, Some(1), Some(0))
+                    .try_seal_with_senders()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_matches!(
+            provider.append_blocks_with_state_no_trie(
+                blocks.clone(),
+                BundleStateWithReceipts::default(),
+                None
+            ),
+            Ok(())
+        );
+
+        for block in blocks {
+            assert_eq!(provider.block(block.number.into()).unwrap(), Some(block.block.unseal()));
+        }
+        for stage_id in [StageId::Headers, StageId::Execution, StageId::IndexAccountHistory] {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage_id).unwrap(),
+                Some(StageCheckpoint::new(2))
+            );
+        }
+        for stage_id in [StageId::AccountHashing, StageId::MerkleExecute, StageId::Finish] {
+            assert_eq!(provider.get_stage_checkpoint(stage_id).unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn finished_head_header() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut headers = Vec::new();
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, None, Some(0), Some(0));
+            headers.push(block.header.clone());
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(1)).unwrap();
+
+        assert_eq!(provider.finished_head_header().unwrap(), Some(headers[1].clone()));
+        assert_eq!(provider.latest_sealed_header().unwrap(), Some(headers[2].clone()));
+    }
+
+    #[test]
+    fn account_nonce_and_balance() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let address = Address::with_last_byte(1);
+        let before = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let after = Account { nonce: 2, balance: U256::from(20), bytecode_hash: None };
+        provider.tx_ref().put::<tables::PlainAccountState>(address, after).unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::AccountChangeSets>(1, AccountBeforeTx { address, info: Some(before) })
+            .unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::AccountsHistory>(
+                ShardedKey::last(address),
+                BlockNumberList::new([1]).unwrap(),
+            )
+            .unwrap();
+        for number in 0..2 {
+            provider
+                .tx_ref()
+                .put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8))
+                .unwrap();
+        }
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(1)).unwrap();
+
+        assert_eq!(provider.account_nonce(address).unwrap(), Some(2));
+        assert_eq!(provider.account_balance(address).unwrap(), Some(U256::from(20)));
+        assert_eq!(provider.account_nonce(Address::ZERO).unwrap(), None);
+        assert_eq!(provider.account_balance(Address::ZERO).unwrap(), None);
+        provider.commit().unwrap();
+
+        let nonce_and_balance_at = |address, block_number| {
+            factory.provider().unwrap().account_nonce_and_balance_at(address, block_number).unwrap()
+        };
+        assert_eq!(nonce_and_balance_at(address, 0), Some((1, U256::from(10))));
+        assert_eq!(nonce_and_balance_at(address, 1), Some((2, U256::from(20))));
+        assert_eq!(nonce_and_balance_at(Address::ZERO, 1), None);
+    }
+
+    #[test]
+    fn canonical_hashes_with_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let hashes = (0..4).map(B256::with_last_byte).collect::<Vec<_>>();
+        for (number, hash) in hashes.iter().enumerate() {
+            provider.tx_ref().put::<tables::CanonicalHeaders>(number as u64, *hash).unwrap();
+        }
+
+        assert_eq!(provider.canonical_hashes_with_range(1..3).unwrap(), hashes[1..3]);
+        assert_eq!(provider.canonical_hashes_with_range(1..=3).unwrap(), hashes[1..=3]);
+        assert_eq!(
+            provider.canonical_hashes_with_range(1..=3).unwrap(),
+            provider.canonical_hashes_range(1, 4).unwrap()
+        );
+        assert_eq!(provider.canonical_hashes_with_range(2..).unwrap(), hashes[2..]);
+        assert_eq!(provider.canonical_hashes_with_range(..).unwrap(), hashes);
+    }
+
+    #[test]
+    fn block_range_limited() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut blocks = Vec::new();
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, None, Some(1), Some(0));
+            assert_matches!(
+                provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+            blocks.push(block.unseal());
+        }
+
+        assert_eq!(provider.block_range_limited(0, 2).unwrap(), blocks[..2]);
+        // Only available blocks are returned
+        assert_eq!(provider.block_range_limited(1, 5).unwrap(), blocks[1..]);
+        assert!(provider.block_range_limited(0, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn prune_changesets() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let storage_key = B256::with_last_byte(1);
+        for number in 1..4 {
+            for address in [Address::with_last_byte(1), Address::with_last_byte(2)] {
+                provider
+                    .tx_ref()
+                    .put::<tables::AccountChangeSets>(
+                        number,
+                        AccountBeforeTx { address, info: None },
+                    )
+                    .unwrap();
+                provider
+                    .tx_ref()
+                    .put::<tables::StorageChangeSets>(
+                        BlockNumberAddress((number, address)),
+                        StorageEntry { key: storage_key, value: U256::ZERO },
+                    )
+                    .unwrap();
+            }
+        }
+        let address = Address::with_last_byte(1);
+        provider
+            .tx_ref()
+            .put::<tables::AccountsHistory>(
+                ShardedKey::last(address),
+                BlockNumberList::new_pre_sorted([1, 2, 3]),
+            )
+            .unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::StoragesHistory>(
+                StorageShardedKey::last(address, storage_key),
+                BlockNumberList::new_pre_sorted([1, 2, 3]),
+            )
+            .unwrap();
+
+        let prune_mode = PruneMode::Before(3);
+        assert_eq!(
+            provider
+                .prune_account_changesets(1..=2, prune_mode, &mut PruneLimiter::default())
+                .unwrap(),
+            (4, true)
+        );
+        assert_eq!(
+            provider
+                .get_prune_checkpoint(PruneSegment::AccountHistory)
+                .unwrap()
+                .and_then(|c| c.block_number),
+            Some(2)
+        );
+        assert_eq!(provider.table::<tables::AccountChangeSets>().unwrap().len(), 2);
+        // The history indices are pruned up to the checkpoint as well
+        assert_eq!(
+            provider
+                .tx_ref()
+                .get::<tables::AccountsHistory>(ShardedKey::last(address))
+                .unwrap()
+                .map(|blocks| blocks.iter().collect::<Vec<_>>()),
+            Some(vec![3])
+        );
+
+        // The limit is reached in the middle of block 2, which is pruned on the next run
+        let mut limiter = PruneLimiter::default().set_deleted_entries_limit(3);
+        assert_eq!(
+            provider.prune_storage_changesets(1..=2, prune_mode, &mut limiter).unwrap(),
+            (3, false)
+        );
+        assert_eq!(
+            provider
+                .get_prune_checkpoint(PruneSegment::StorageHistory)
+                .unwrap()
+                .and_then(|c| c.block_number),
+            Some(1)
+        );
+        assert_eq!(
+            provider
+                .tx_ref()
+                .get::<tables::StoragesHistory>(StorageShardedKey::last(address, storage_key))
+                .unwrap()
+                .map(|blocks| blocks.iter().collect::<Vec<_>>()),
+            Some(vec![2, 3])
+        );
+        assert_eq!(
+            provider
+                .prune_storage_changesets(2..=2, prune_mode, &mut PruneLimiter::default())
+                .unwrap(),
+            (1, true)
+        );
+        assert_eq!(
+            provider
+                .get_prune_checkpoint(PruneSegment::StorageHistory)
+                .unwrap()
+                .and_then(|c| c.block_number),
+            Some(2)
+        );
+        assert_eq!(provider.table::<tables::StorageChangeSets>().unwrap().len(), 2);
+        assert_eq!(
+            provider
+                .tx_ref()
+                .get::<tables::StoragesHistory>(StorageShardedKey::last(address, storage_key))
+                .unwrap()
+                .map(|blocks| blocks.iter().collect::<Vec<_>>()),
+            Some(vec![3])
+        );
+    }
+
+    #[test]
+    fn unwind_table_by_walker_limited() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        for number in 0..4 {
+            let hash = B256::with_last_byte(number as u8);
+            provider.tx_ref().put::<tables::CanonicalHeaders>(number, hash).unwrap();
+            provider.tx_ref().put::<tables::HeaderNumbers>(hash, number).unwrap();
+        }
+
+        let mut limiter = PruneLimiter::default().set_deleted_entries_limit(2);
+        assert_eq!(
+            provider
+                .unwind_table_by_walker_limited::<tables::CanonicalHeaders, tables::HeaderNumbers>(
+                    1,
+                    &mut limiter
+                )
+                .unwrap(),
+            (Some(2), false)
+        );
+
+        // Resume from the last processed key
+        let mut limiter = PruneLimiter::default();
+        assert_eq!(
+            provider
+                .unwind_table_by_walker_limited::<tables::CanonicalHeaders, tables::HeaderNumbers>(
+                    2,
+                    &mut limiter
+                )
+                .unwrap(),
+            (Some(3), true)
+        );
+
+        assert_eq!(provider.tx_ref().entries::<tables::HeaderNumbers>().unwrap(), 1);
+        assert_eq!(
+            provider.tx_ref().get::<tables::HeaderNumbers>(B256::with_last_byte(0)).unwrap(),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn prune_table_with_range_resumable() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        for number in 0..5 {
+            provider
+                .tx_ref()
+                .put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8))
+                .unwrap();
+        }
+
+        let mut limiter = PruneLimiter::default().set_deleted_entries_limit(2);
+        assert_eq!(
+            provider
+                .prune_table_with_range_resumable::<tables::CanonicalHeaders>(
+                    0..5,
+                    &mut limiter,
+                    |_| false,
+                    |_| {}
+                )
+                .unwrap(),
+            (2, false, Some(1))
+        );
+
+        // Resume right after the last processed key
+        let mut limiter = PruneLimiter::default();
+        assert_eq!(
+            provider
+                .prune_table_with_range_resumable::<tables::CanonicalHeaders>(
+                    (Bound::Excluded(1), Bound::Excluded(5)),
+                    &mut limiter,
+                    |_| false,
+                    |_| {}
+                )
+                .unwrap(),
+            (3, true, Some(4))
+        );
+
+        assert_eq!(provider.tx_ref().entries::<tables::CanonicalHeaders>().unwrap(), 0);
+    }
+
+    #[test]
+    fn prune_table_with_range_seeking() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        for number in 0..10 {
+            provider
+                .tx_ref()
+                .put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8))
+                .unwrap();
+        }
+
+        // Only every third block is deletable
+        let mut visited = Vec::new();
+        let mut next_deletable_key = |(number, _): &(BlockNumber, B256)| {
+            visited.push(*number);
+            (number % 3 != 0).then(|| (number / 3 + 1) * 3)
+        };
+
+        let mut limiter = PruneLimiter::default().set_deleted_entries_limit(2);
+        assert_eq!(
+            provider
+                .prune_table_with_range_seeking::<tables::CanonicalHeaders>(
+                    0..10,
+                    &mut limiter,
+                    &mut next_deletable_key,
+                    |_| {}
+                )
+                .unwrap(),
+            (2, false)
+        );
+
+        let mut deleted = Vec::new();
+        assert_eq!(
+            provider
+                .prune_table_with_range_seeking::<tables::CanonicalHeaders>(
+                    4..10,
+                    &mut PruneLimiter::default(),
+                    &mut next_deletable_key,
+                    |(number, _)| deleted.push(number)
+                )
+                .unwrap(),
+            (2, true)
+        );
+        assert_eq!(deleted, vec![6, 9]);
+
+        // Kept rows between deletable ones were skipped over
+        assert_eq!(visited, vec![0, 1, 3, 4, 6, 7, 9]);
+        assert_eq!(provider.tx_ref().entries::<tables::CanonicalHeaders>().unwrap(), 6);
+    }
+
+    #[test]
+    fn get_or_take_rev() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        for block_number in 0..4 {
+            for address in 0..2 {
+                provider
+                    .tx_ref()
+                    .put::<tables::AccountChangeSets>(
+                        block_number,
+                        AccountBeforeTx { address: Address::with_last_byte(address), info: None },
+                    )
+                    .unwrap();
+            }
+        }
+
+        let reversed = |range: RangeInclusive<BlockNumber>| {
+            let mut entries =
+                provider.get_or_take::<tables::AccountChangeSets, false>(range).unwrap();
+            entries.reverse();
+            entries
+        };
+
+        assert_eq!(
+            provider.get_or_take_rev::<tables::AccountChangeSets, false>(1..=2).unwrap(),
+            reversed(1..=2)
+        );
+        assert_eq!(
+            provider.get_or_take_rev::<tables::AccountChangeSets, false>(1..3).unwrap(),
+            reversed(1..=2)
+        );
+        assert_eq!(
+            provider.get_or_take_rev::<tables::AccountChangeSets, false>(..).unwrap(),
+            reversed(0..=3)
+        );
+        assert!(provider
+            .get_or_take_rev::<tables::AccountChangeSets, false>(5..)
+            .unwrap()
+            .is_empty());
+
+        let expected = reversed(1..=2);
+        assert_eq!(
+            provider.get_or_take_rev::<tables::AccountChangeSets, true>(1..=2).unwrap(),
+            expected
+        );
+        assert!(provider
+            .get_or_take::<tables::AccountChangeSets, false>(1..=2)
+            .unwrap()
+            .is_empty());
+        assert_eq!(provider.get_or_take::<tables::AccountChangeSets, false>(..).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn get_or_take_filtered() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        for block_number in 0..4 {
+            provider
+                .tx_ref()
+                .put::<tables::CanonicalHeaders>(
+                    block_number,
+                    B256::with_last_byte(block_number as u8),
+                )
+                .unwrap();
+        }
+        let keep_genesis = |(block_number, _): &(BlockNumber, B256)| *block_number == 0;
+
+        let expected = provider.get_or_take::<tables::CanonicalHeaders, false>(1..).unwrap();
+        assert_eq!(
+            provider
+                .get_or_take_filtered::<tables::CanonicalHeaders, false>(.., keep_genesis)
+                .unwrap(),
+            expected
+        );
+        assert_eq!(provider.get_or_take::<tables::CanonicalHeaders, false>(..).unwrap().len(), 4);
+
+        assert_eq!(
+            provider
+                .get_or_take_filtered::<tables::CanonicalHeaders, true>(.., keep_genesis)
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            provider.get_or_take::<tables::CanonicalHeaders, false>(..).unwrap(),
+            vec![(0, B256::with_last_byte(0))]
+        );
+    }
+
+    #[test]
+    fn block_body_indices_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut expected = Vec::new();
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, None, Some(2), Some(0));
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+            expected.push((number, provider.block_body_indices(number).unwrap().unwrap()));
+        }
+
+        assert_eq!(provider.block_body_indices_range(0..=2).unwrap(), expected);
+        assert_eq!(provider.block_body_indices_range(1..=5).unwrap(), expected[1..]);
+        assert!(provider.block_body_indices_range(3..=5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn storage_root_for_account() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let address = Address::with_last_byte(1);
+        let storage = (1..=3u64)
+            .map(|slot| StorageEntry {
+                key: B256::with_last_byte(slot as u8),
+                value: U256::from(slot),
+            })
+            .collect::<Vec<_>>();
+        provider.insert_storage_for_hashing([(address, storage.clone())]).unwrap();
+
+        assert_eq!(
+            provider.storage_root_for_account(keccak256(address)).unwrap(),
+            storage_root_unhashed(storage.into_iter().map(|entry| (entry.key, entry.value)))
+        );
+        assert_eq!(
+            provider.storage_root_for_account(keccak256(Address::with_last_byte(2))).unwrap(),
+            EMPTY_ROOT_HASH
+        );
+    }
+
+    #[test]
+    fn verify_account_storage_roots() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let storage = |value: u64| {
+            (1..=3u8)
+                .map(|slot| StorageEntry {
+                    key: B256::with_last_byte(slot),
+                    value: U256::from(value),
+                })
+                .collect::<Vec<_>>()
+        };
+        let storage_root = |storage: Vec<StorageEntry>| {
+            storage_root_unhashed(storage.into_iter().map(|entry| (entry.key, entry.value)))
+        };
+
+        for i in 1..=3 {
+            provider
+                .tx_ref()
+                .put::<tables::PlainAccountState>(Address::with_last_byte(i), Account::default())
+                .unwrap();
+        }
+        for i in 1..=2 {
+            for entry in storage(1) {
+                provider
+                    .tx_ref()
+                    .put::<tables::PlainStorageState>(Address::with_last_byte(i), entry)
+                    .unwrap();
+            }
+        }
+        // Hashed storage of the second account diverged from its plain storage
+        provider
+            .insert_storage_for_hashing([
+                (Address::with_last_byte(1), storage(1)),
+                (Address::with_last_byte(2), storage(2)),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            provider.verify_account_storage_roots(..).unwrap(),
+            vec![(
+                Address::with_last_byte(2),
+                GotExpected { got: storage_root(storage(2)), expected: storage_root(storage(1)) }
+            )]
+        );
+        assert!(provider
+            .verify_account_storage_roots(Address::with_last_byte(3)..)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn withdrawals_root_by_block() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let withdrawals = vec![
+            Withdrawal {
+                index: 0,
+                validator_index: 1,
+                address: Address::with_last_byte(1),
+                amount: 2,
+            },
+            Withdrawal {
+                index: 1,
+                validator_index: 2,
+                address: Address::with_last_byte(2),
+                amount: 3,
+            },
+        ];
+        provider
+            .tx_ref()
+            .put::<tables::BlockWithdrawals>(
+                1,
+                StoredBlockWithdrawals { withdrawals: Withdrawals::new(withdrawals.clone()) },
+            )
+            .unwrap();
+
+        let shanghai_timestamp = u64::MAX;
+        // Pre-Shanghai blocks have no withdrawals root
+        assert_eq!(provider.withdrawals_root_by_block(1.into(), 0).unwrap(), None);
+        assert_eq!(
+            provider.withdrawals_root_by_block(1.into(), shanghai_timestamp).unwrap(),
+            Some(calculate_withdrawals_root(&withdrawals))
+        );
+        // Empty withdrawal list yields the empty trie root
+        assert_eq!(
+            provider.withdrawals_root_by_block(2.into(), shanghai_timestamp).unwrap(),
+            Some(EMPTY_ROOT_HASH)
+        );
+    }
+
+    #[test]
+    fn remove_block() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let blocks = (0..2)
+            .map(|number| random_block(&mut rng, number, None, Some(2), Some(1)))
+            .collect::<Vec<_>>();
+        for block in &blocks {
+            assert_matches!(
+                provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        assert_matches!(provider.remove_block(0), Err(ProviderError::NotTipBlock(0, 1)));
+
+        assert_matches!(provider.remove_block(1), Ok(()));
+        assert_eq!(provider.last_block_number().unwrap(), 0);
+        assert_eq!(provider.block(1.into()).unwrap(), None);
+        assert_eq!(provider.block_body_indices(1).unwrap(), None);
+        assert_eq!(provider.block_number(blocks[1].hash()).unwrap(), None);
+        for tx in &blocks[1].body {
+            assert_eq!(provider.transaction_id(tx.hash()).unwrap(), None);
+        }
+        assert!(provider.transactions_by_tx_range(2..4).unwrap().is_empty());
+
+        // Parent block is left intact
+        assert_eq!(provider.block(0.into()).unwrap(), Some(blocks[0].clone().unseal()));
+    }
+
+    #[test]
+    fn finalized_block_number() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, None, Some(0), Some(0));
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+        assert_eq!(provider.finalized_block_number().unwrap(), None);
+
+        provider.save_finalized_block_number(1).unwrap();
+        assert_eq!(provider.finalized_block_number().unwrap(), Some(1));
+
+        // Unwinding above the finalized block keeps it
+        assert_matches!(provider.remove_block(2), Ok(()));
+        assert_eq!(provider.finalized_block_number().unwrap(), Some(1));
+
+        // Unwinding the finalized block clears it
+        assert_matches!(provider.remove_block(1), Ok(()));
+        assert_eq!(provider.finalized_block_number().unwrap(), None);
+    }
+
+    #[test]
+    fn append_receipts() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let blocks = (0..3)
+            .map(|number| random_block(&mut rng, number, None, Some(number as u8), Some(0)))
+            .collect::<Vec<_>>();
+        let receipts = blocks
+            .iter()
+            .map(|block| {
+                block
+                    .body
+                    .iter()
+                    .map(|transaction| random_receipt(&mut rng, transaction, Some(1)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_matches!(provider.append_receipts(0, receipts[..2].to_vec()), Ok(()));
+        assert_matches!(
+            provider.append_receipts(0, receipts[2..].to_vec()),
+            Err(ProviderError::UnexpectedReceiptNumber(GotExpected { got: 0, expected: 1 }))
+        );
+        assert_matches!(provider.append_receipts(1, receipts[2..].to_vec()), Ok(()));
+
+        assert_eq!(
+            provider.table::<tables::Receipts>().unwrap(),
+            (0..).zip(receipts.into_iter().flatten()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn get_take_block_range_sealed_headers() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut expected = Vec::new();
+        let mut parent = None;
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, parent, Some(2), Some(0))
+                .try_seal_with_senders()
+                .unwrap();
+            parent = Some(block.hash());
+            expected.push((block.header.clone(), block.body.clone(), block.senders.clone()));
+            assert_matches!(provider.insert_block(block, None), Ok(_));
+        }
+        let components = |blocks: Vec<SealedBlockWithSenders>| {
+            blocks
+                .into_iter()
+                .map(|block| (block.block.header, block.block.body, block.senders))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            components(provider.get_take_block_range::<false>(1..=2).unwrap()),
+            expected[1..]
+        );
+        assert_eq!(
+            components(provider.get_take_block_range::<true>(1..=2).unwrap()),
+            expected[1..]
+        );
+        assert!(provider.get_take_block_range::<false>(1..=2).unwrap().is_empty());
+        assert_eq!(components(provider.get_take_block_range::<false>(..).unwrap()), expected[..1]);
+    }
+
+    #[test]
+    fn insert_block_checked() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(0), Some(0));
+        let other = random_block(&mut rng, 0, None, Some(0), Some(0));
+        let child = random_block(&mut rng, 1, Some(block.hash()), Some(0), Some(0));
+        assert_matches!(
+            provider.insert_block_checked(block.clone().try_seal_with_senders().unwrap(), None),
+            Ok(_)
+        );
+
+        // Inserting the same block again is fine
+        assert_matches!(
+            provider.insert_block_checked(block.clone().try_seal_with_senders().unwrap(), None),
+            Ok(_)
+        );
+
+        assert_eq!(
+            provider.insert_block_checked(other.clone().try_seal_with_senders().unwrap(), None),
+            Err(ProviderError::BlockNumberOccupied {
+                block_number: 0,
+                existing: block.hash(),
+                hash: other.hash()
+            })
+        );
+        assert_eq!(provider.block_hash(0).unwrap(), Some(block.hash()));
+
+        assert_matches!(
+            provider.insert_block_checked(child.try_seal_with_senders().unwrap(), None),
+            Ok(_)
+        );
+    }
+
+    #[test]
+    fn insert_block_with_hashes() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), Some(0));
+        let expected_hashes = block.body.iter().map(|tx| tx.hash()).collect::<Vec<_>>();
+
+        let (block_indices, tx_hashes) = provider
+            .insert_block_with_hashes(block.try_seal_with_senders().unwrap(), None)
+            .unwrap();
+        assert_eq!(block_indices, StoredBlockBodyIndices { first_tx_num: 0, tx_count: 3 });
+        assert_eq!(tx_hashes, expected_hashes);
+        for (tx_num, hash) in tx_hashes.into_iter().enumerate() {
+            assert_eq!(provider.transaction_id(hash).unwrap(), Some(tx_num as TxNumber));
+        }
+    }
+
+    #[test]
+    fn receipts_by_block_across_static_file_boundary() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), Some(0));
+        let receipts = block
+            .body
+            .iter()
+            .map(|transaction| random_receipt(&mut rng, transaction, Some(0)))
+            .collect::<Vec<_>>();
+        assert_matches!(provider.insert_block(block.try_seal_with_senders().unwrap(), None), Ok(_));
+
+        // First two receipts of the block are in static files, the last one in the database
+        let mut static_file_writer =
+            provider.static_file_provider().latest_writer(StaticFileSegment::Receipts).unwrap();
+        static_file_writer.increment_block(StaticFileSegment::Receipts, 0).unwrap();
+        for (tx_num, receipt) in receipts.iter().take(2).enumerate() {
+            static_file_writer.append_receipt(tx_num as TxNumber, receipt.clone()).unwrap();
+        }
+        static_file_writer.commit().unwrap();
+        drop(static_file_writer);
+        provider.tx_ref().put::<tables::Receipts>(2, receipts[2].clone()).unwrap();
+
+        assert_eq!(provider.receipts_by_block(0.into()).unwrap(), Some(receipts));
+    }
+
+    #[test]
+    fn receipts_by_block_with_bloom() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(2), Some(0));
+        let receipts = block
+            .body
+            .iter()
+            .map(|transaction| random_receipt(&mut rng, transaction, Some(2)))
+            .collect::<Vec<_>>();
+        for (tx_num, receipt) in receipts.iter().enumerate() {
+            provider.tx_ref().put::<tables::Receipts>(tx_num as TxNumber, receipt.clone()).unwrap();
+        }
+        let empty_block = random_block(&mut rng, 1, Some(block.hash()), Some(0), Some(0));
+        assert_matches!(provider.insert_block(block.try_seal_with_senders().unwrap(), None), Ok(_));
+        assert_matches!(
+            provider.insert_block(empty_block.try_seal_with_senders().unwrap(), None),
+            Ok(_)
+        );
+
+        let receipt_blooms = receipts.iter().map(Receipt::bloom_slow).collect::<Vec<_>>();
+        let expected_bloom = block_bloom(&receipt_blooms);
+        assert_eq!(
+            provider.receipts_by_block_with_bloom(0u64.into()).unwrap(),
+            Some((receipts, expected_bloom))
+        );
+        assert_eq!(
+            provider.receipts_by_block_with_bloom(1u64.into()).unwrap(),
+            Some((Vec::new(), Bloom::ZERO))
+        );
+        assert_eq!(provider.receipts_by_block_with_bloom(2u64.into()).unwrap(), None);
+    }
+
+    #[test]
+    fn prune_receipts_by_block_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut tx_num = 0;
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, None, Some(2), Some(0));
+            for transaction in &block.body {
+                let receipt = random_receipt(&mut rng, transaction, Some(0));
+                provider.tx_ref().put::<tables::Receipts>(tx_num, receipt).unwrap();
+                tx_num += 1;
+            }
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        let prune_mode = PruneMode::Before(2);
+
+        // Limit is reached after the first receipt of block 1
+        let mut limiter = PruneLimiter::default().set_deleted_entries_limit(3);
+        assert_matches!(
+            provider.prune_receipts_by_block_range(0..=1, prune_mode, &mut limiter),
+            Ok((3, false))
+        );
+        assert_eq!(
+            provider.get_prune_checkpoint(PruneSegment::Receipts).unwrap(),
+            Some(PruneCheckpoint { block_number: Some(0), tx_number: Some(2), prune_mode })
+        );
+
+        let mut limiter = PruneLimiter::default();
+        assert_matches!(
+            provider.prune_receipts_by_block_range(1..=1, prune_mode, &mut limiter),
+            Ok((1, true))
+        );
+        assert_eq!(
+            provider.get_prune_checkpoint(PruneSegment::Receipts).unwrap(),
+            Some(PruneCheckpoint { block_number: Some(1), tx_number: Some(3), prune_mode })
+        );
+        assert_eq!(provider.receipts_by_tx_range(0..6).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn prune_transaction_lookup_by_block_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut hashes = Vec::new();
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, None, Some(2), Some(0));
+            hashes.extend(block.body.iter().map(|tx| tx.hash()));
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        let prune_mode = PruneMode::Before(3);
+
+        // Limit is reached after the first transaction of block 1
+        let mut limiter = PruneLimiter::default().set_deleted_entries_limit(3);
+        assert_matches!(
+            provider.prune_transaction_lookup_by_block_range(0..=2, prune_mode, &mut limiter),
+            Ok((3, false))
+        );
+        assert_eq!(
+            provider.get_prune_checkpoint(PruneSegment::TransactionLookup).unwrap(),
+            Some(PruneCheckpoint { block_number: Some(0), tx_number: Some(2), prune_mode })
+        );
+
+        let mut limiter = PruneLimiter::default();
+        assert_matches!(
+            provider.prune_transaction_lookup_by_block_range(1..=2, prune_mode, &mut limiter),
+            Ok((3, true))
+        );
+        assert_eq!(
+            provider.get_prune_checkpoint(PruneSegment::TransactionLookup).unwrap(),
+            Some(PruneCheckpoint { block_number: Some(2), tx_number: Some(5), prune_mode })
+        );
+        for hash in hashes {
+            assert_eq!(provider.transaction_id(hash).unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn prune_transaction_lookup_for_tx_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let mut hashes = Vec::new();
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, None, Some(2), Some(0));
+            hashes.extend(block.body.iter().map(|tx| tx.hash()));
+            assert_matches!(
+                provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+                Ok(_)
+            );
+        }
+
+        let prune_mode = PruneMode::Before(3);
+
+        // Limit is reached after the first transaction of block 1
+        let mut limiter = PruneLimiter::default().set_deleted_entries_limit(3);
+        assert_matches!(
+            provider.prune_transaction_lookup_for_tx_range(0..=4, prune_mode, &mut limiter),
+            Ok((3, false))
+        );
+        assert_eq!(
+            provider.get_prune_checkpoint(PruneSegment::TransactionLookup).unwrap(),
+            Some(PruneCheckpoint { block_number: Some(0), tx_number: Some(2), prune_mode })
+        );
+
+        let mut limiter = PruneLimiter::default();
+        assert_matches!(
+            provider.prune_transaction_lookup_for_tx_range(3..=3, prune_mode, &mut limiter),
+            Ok((1, true))
+        );
+        assert_eq!(
+            provider.get_prune_checkpoint(PruneSegment::TransactionLookup).unwrap(),
+            Some(PruneCheckpoint { block_number: Some(1), tx_number: Some(3), prune_mode })
+        );
+        for (tx_num, hash) in hashes.into_iter().enumerate() {
+            assert_eq!(provider.transaction_id(hash).unwrap().is_none(), tx_num <= 3);
+        }
+
+        // Transactions missing from the range are reported
+        assert_matches!(
+            provider.prune_transaction_lookup_for_tx_range(4..=6, prune_mode, &mut limiter),
+            Err(ProviderError::TransactionNotFound(_))
+        );
+
+        // Only the transactions that fit in the limit are read, so the missing ones aren't reached
+        let mut limiter = PruneLimiter::default().set_deleted_entries_limit(1);
+        assert_matches!(
+            provider.prune_transaction_lookup_for_tx_range(4..=6, prune_mode, &mut limiter),
+            Ok((1, false))
+        );
+    }
+
+    #[test]
+    fn header_sync_gap_lookup() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let consensus_tip = rng.gen();
+        let (_tip_tx, tip_rx) = watch::channel(consensus_tip);
+        let mode = HeaderSyncMode::Tip(tip_rx);
+
+        // Genesis
+        let checkpoint = 0;
+        let head = random_header(&mut rng, 0, None);
+
+        // Empty database
+        assert_matches!(
+            provider.sync_gap(mode.clone(), checkpoint),
+            Err(RethError::Provider(ProviderError::HeaderNotFound(block_number)))
+                if block_number.as_number().unwrap() == checkpoint
+        );
+
+        // Checkpoint and no gap
+        provider
+            .with_static_file_writer(StaticFileSegment::Headers, |writer| {
+                writer.append_header(head.header().clone(), U256::ZERO, head.hash())
+            })
+            .unwrap();
 
         let gap = provider.sync_gap(mode, checkpoint).unwrap();
         assert_eq!(gap.local_head, head);
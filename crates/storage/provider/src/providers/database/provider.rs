@@ -1,6 +1,8 @@
 use crate::{
     bundle_state::{BundleStateInit, BundleStateWithReceipts, HashedStateChanges, RevertsInit},
-    providers::{database::metrics, static_file::StaticFileWriter, StaticFileProvider},
+    providers::{
+        database::metrics, static_file::StaticFileWriter, StaticFileProvider, StaticFileProviderRW,
+    },
     to_range,
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
@@ -12,10 +14,11 @@ use crate::{
     StageCheckpointReader, StateProviderBox, StatsReader, StorageReader, TransactionVariant,
     TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
 };
-use itertools::{izip, Itertools};
+use itertools::Itertools;
+use rayon::prelude::*;
 use reth_db::{
     common::KeyValue,
-    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, RangeWalker},
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, RangeWalker, ReverseWalker},
     database::Database,
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
@@ -33,20 +36,21 @@ use reth_interfaces::{
     RethResult,
 };
 use reth_primitives::{
-    keccak256,
+    keccak256, logs_bloom, proofs,
     revm::{config::revm_spec, env::fill_block_env},
     stage::{StageCheckpoint, StageId},
     trie::Nibbles,
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
-    ChainInfo, ChainSpec, GotExpected, Head, Header, PruneCheckpoint, PruneLimiter, PruneModes,
-    PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment,
-    StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bloom,
+    ChainInfo, ChainSpec, GotExpected, Head, Header, PruneCheckpoint, PruneLimiter, PruneMode,
+    PruneModes, PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    StaticFileSegment, StorageEntry, TransactionMeta, TransactionSigned,
+    TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
+    Withdrawals, B256, U256,
 };
 use reth_trie::{
     prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
     updates::TrieUpdates,
-    HashedPostState, StateRoot,
+    HashedPostState, StateRoot, StorageRoot,
 };
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg, SpecId};
 use std::{
@@ -59,6 +63,10 @@ use std::{
 };
 use tracing::{debug, error, warn};
 
+/// Maximum number of blocks whose transactions are loaded at once by
+/// [`DatabaseProvider::get_take_block_transaction_range`].
+const BLOCK_TRANSACTION_RANGE_CHUNK_SIZE: usize = 10_000;
+
 /// A [`DatabaseProvider`] that holds a read-only database transaction.
 pub type DatabaseProviderRO<DB> = DatabaseProvider<<DB as Database>::TX>;
 
@@ -93,6 +101,22 @@ impl<DB: Database> DatabaseProviderRW<DB> {
     pub fn into_tx(self) -> <DB as Database>::TXMut {
         self.0.into_tx()
     }
+
+    /// Runs `f` with this provider and commits the database transaction if it returns `Ok`.
+    ///
+    /// If `f` returns an error, the provider is dropped without being committed, which aborts the
+    /// transaction and discards all writes made by `f`.
+    ///
+    /// This is the preferred pattern for fallible write sequences, since an early return can't
+    /// silently drop writes that were meant to be committed.
+    pub fn commit_with<T, E>(self, f: impl FnOnce(&Self) -> Result<T, E>) -> Result<T, E>
+    where
+        E: From<ProviderError>,
+    {
+        let output = f(&self)?;
+        self.commit()?;
+        Ok(output)
+    }
 }
 
 /// A provider struct that fetches data from the database.
@@ -105,6 +129,8 @@ pub struct DatabaseProvider<TX> {
     chain_spec: Arc<ChainSpec>,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Whether [`BlockWriter::insert_block`] records fine-grained per-action durations.
+    insert_block_metrics: bool,
 }
 
 impl<TX> DatabaseProvider<TX> {
@@ -112,6 +138,43 @@ impl<TX> DatabaseProvider<TX> {
     pub fn static_file_provider(&self) -> &StaticFileProvider {
         &self.static_file_provider
     }
+
+    /// Replaces the [ChainSpec] of the provider.
+    ///
+    /// This allows opening a provider before the chain is known, e.g. to read the stored genesis,
+    /// and then applying the matching spec.
+    pub fn with_chain_spec(mut self, chain_spec: Arc<ChainSpec>) -> Self {
+        self.chain_spec = chain_spec;
+        self
+    }
+
+    /// Enables or disables the per-action duration metrics recorded by
+    /// [`BlockWriter::insert_block`]. Enabled by default.
+    ///
+    /// Bulk importers that write many blocks can disable them to avoid the timing overhead on
+    /// every insertion.
+    pub fn set_insert_block_metrics(&mut self, enabled: bool) {
+        self.insert_block_metrics = enabled;
+    }
+
+    /// Runs `f` with the latest static file writer of the given segment, and commits the writer if
+    /// `f` returns `Ok`.
+    ///
+    /// Static files are not part of the database transaction. The writes are committed to disk
+    /// here, independently of [`DatabaseProvider::commit`]. If the database transaction is dropped
+    /// afterwards, the static files are ahead of the database, which is healed by the consistency
+    /// checks on the next startup. If `f` returns an error, the writer is not committed, but any
+    /// data it appended stays buffered and is committed by the next commit of the writer.
+    pub fn with_static_file_writer<R>(
+        &self,
+        segment: StaticFileSegment,
+        f: impl FnOnce(&mut StaticFileProviderRW) -> ProviderResult<R>,
+    ) -> ProviderResult<R> {
+        let mut writer = self.static_file_provider.latest_writer(segment)?;
+        let output = f(&mut writer)?;
+        writer.commit()?;
+        Ok(output)
+    }
 }
 
 impl<TX: DbTxMut> DatabaseProvider<TX> {
@@ -121,7 +184,7 @@ impl<TX: DbTxMut> DatabaseProvider<TX> {
         chain_spec: Arc<ChainSpec>,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider }
+        Self { tx, chain_spec, static_file_provider, insert_block_metrics: true }
     }
 }
 
@@ -207,6 +270,21 @@ impl<TX: DbTx + 'static> DatabaseProvider<TX> {
 
         Ok(Box::new(state_provider))
     }
+
+    /// Get the nonce and balance of the account at the given block, or `None` if the account
+    /// didn't exist at that block.
+    ///
+    /// See [`DatabaseProvider::state_provider_by_block_number`] for how the state is looked up.
+    pub fn account_nonce_and_balance_at(
+        self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Option<(u64, U256)>> {
+        Ok(self
+            .state_provider_by_block_number(block_number)?
+            .basic_account(address)?
+            .map(|account| (account.nonce, account.balance)))
+    }
 }
 
 impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
@@ -291,6 +369,43 @@ where
     Ok(Vec::new())
 }
 
+/// Returns a [`ReverseWalker`] positioned at the last entry within the end bound of the range.
+///
+/// The walker doesn't check the start bound of the range, callers have to stop the walk once a
+/// key falls out of it.
+fn walk_range_back<'a, T, C>(
+    cursor: &'a mut C,
+    range: &impl RangeBounds<T::Key>,
+) -> Result<ReverseWalker<'a, T, C>, DatabaseError>
+where
+    T: Table,
+    C: DbCursorRO<T>,
+{
+    let start = match range.end_bound() {
+        Bound::Included(key) => {
+            // Move past all entries of the end key, so that duplicates are included.
+            let mut entry = cursor.seek(key.clone())?;
+            while entry.as_ref().is_some_and(|(k, _)| k == key) {
+                entry = cursor.next()?;
+            }
+            if entry.is_some() {
+                cursor.prev()?
+            } else {
+                cursor.last()?
+            }
+        }
+        Bound::Excluded(key) => {
+            if cursor.seek(key.clone())?.is_some() {
+                cursor.prev()?
+            } else {
+                cursor.last()?
+            }
+        }
+        Bound::Unbounded => cursor.last()?,
+    };
+    Ok(ReverseWalker::new(cursor, start.map(Ok)))
+}
+
 impl<TX: DbTx> DatabaseProvider<TX> {
     /// Creates a provider with an inner read-only transaction.
     pub fn new(
@@ -298,7 +413,7 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         chain_spec: Arc<ChainSpec>,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider }
+        Self { tx, chain_spec, static_file_provider, insert_block_metrics: true }
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -354,171 +469,1184 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             |_| true,
         )
     }
-}
 
-impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
-    /// Commit database transaction.
-    pub fn commit(self) -> ProviderResult<bool> {
-        Ok(self.tx.commit()?)
+    /// Get the transactions in the given range that are stored in static files or the database,
+    /// each paired with its transaction number.
+    ///
+    /// Unlike [`TransactionsProvider::transactions_by_tx_range`], the numbers are taken from the
+    /// rows that were actually read, so gaps in the range, e.g. because of pruning, don't shift
+    /// them. Transactions in static files are contiguous, so their numbers follow from the range.
+    fn transactions_with_numbers_with_cursor(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+        cursor: &mut impl DbCursorRO<tables::Transactions>,
+    ) -> ProviderResult<Vec<(TxNumber, TransactionSignedNoHash)>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::Transactions,
+            to_range(range),
+            |static_file, range, _| {
+                Ok(range.clone().zip(static_file.transactions_by_tx_range(range)?).collect())
+            },
+            |range, _| Ok(cursor.walk_range(range)?.collect::<Result<Vec<_>, _>>()?),
+            |_| true,
+        )
     }
 
-    // TODO(joshie) TEMPORARY should be moved to trait providers
-    /// Unwind or peek at last N blocks of state recreating the [`BundleStateWithReceipts`].
-    ///
-    /// If UNWIND it set to true tip and latest state will be unwind
-    /// and returned back with all the blocks
-    ///
-    /// If UNWIND is false we will just read the state/blocks and return them.
+    /// Looks up the senders of the given transactions in [tables::TransactionSenders] by their
+    /// transaction numbers, which must be in ascending order.
     ///
-    /// 1. Iterate over the [BlockBodyIndices][tables::BlockBodyIndices] table to get all
-    /// the transaction ids.
-    /// 2. Iterate over the [StorageChangeSets][tables::StorageChangeSets] table
-    /// and the [AccountChangeSets][tables::AccountChangeSets] tables in reverse order to
-    /// reconstruct the changesets.
-    ///     - In order to have both the old and new values in the changesets, we also access the
-    ///       plain state tables.
-    /// 3. While iterating over the changeset tables, if we encounter a new account or storage slot,
-    /// we:
-    ///     1. Take the old value from the changeset
-    ///     2. Take the new value from the plain state
-    ///     3. Save the old value to the local state
-    /// 4. While iterating over the changeset tables, if we encounter an account/storage slot we
-    /// have seen before we:
-    ///     1. Take the old value from the changeset
-    ///     2. Take the new value from the local state
-    ///     3. Set the local state to the value in the changeset
-    pub fn unwind_or_peek_state<const UNWIND: bool>(
+    /// If `recover_missing` is set, senders missing from the table, e.g. because they were pruned,
+    /// are recovered from their transactions in parallel. Returns `None` for each sender that is
+    /// missing and wasn't recovered, or failed to recover.
+    fn senders_of_transactions(
         &self,
-        range: RangeInclusive<BlockNumber>,
-    ) -> ProviderResult<BundleStateWithReceipts> {
-        if range.is_empty() {
-            return Ok(BundleStateWithReceipts::default())
-        }
-        let start_block_number = *range.start();
+        transactions: &[(TxNumber, TransactionSignedNoHash)],
+        senders_cursor: &mut impl DbCursorRO<tables::TransactionSenders>,
+        recover_missing: bool,
+    ) -> ProviderResult<Vec<Option<Address>>> {
+        let (Some((first, _)), Some((last, _))) = (transactions.first(), transactions.last())
+        else {
+            return Ok(Vec::new())
+        };
+        let known_senders =
+            senders_cursor.walk_range(*first..=*last)?.collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(transactions
+            .par_iter()
+            .map_init(Vec::new, |rlp_buf, (tx_num, transaction)| {
+                known_senders.get(tx_num).copied().or_else(|| {
+                    recover_missing
+                        .then(|| transaction.encode_and_recover_unchecked(rlp_buf))
+                        .flatten()
+                })
+            })
+            .collect())
+    }
 
-        // We are not removing block meta as it is used to get block changesets.
-        let block_bodies = self.get_or_take::<tables::BlockBodyIndices, false>(range.clone())?;
+    /// Get transactions by tx range, pre-allocating the result with the given `capacity`.
+    ///
+    /// Unlike [`TransactionsProvider::transactions_by_tx_range`], this doesn't derive the capacity
+    /// from the range, which is useful when the number of transactions is already known up front,
+    /// e.g. from the `tx_count` of the block body indices.
+    pub fn transactions_by_tx_range_with_capacity(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+        capacity: usize,
+    ) -> ProviderResult<Vec<TransactionSignedNoHash>> {
+        let mut cursor = self.tx.cursor_read::<tables::Transactions>()?;
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::Transactions,
+            to_range(range),
+            |static_file, range, _| static_file.transactions_by_tx_range(range),
+            |range, _| self.cursor_collect_with_capacity(&mut cursor, range, capacity),
+            |_| true,
+        )
+    }
 
-        // get transaction receipts
-        let from_transaction_num =
-            block_bodies.first().expect("already checked if there are blocks").1.first_tx_num();
-        let to_transaction_num =
-            block_bodies.last().expect("already checked if there are blocks").1.last_tx_num();
+    /// Get the canonical hash of the block with the given number.
+    ///
+    /// Same as [`BlockHashReader::block_hash`], but returns [`ProviderError::HeaderNotFound`] if
+    /// there's no canonical hash for the block, for call sites that expect it to exist.
+    pub fn canonical_hash_checked(&self, number: BlockNumber) -> ProviderResult<B256> {
+        self.block_hash(number)?.ok_or_else(|| ProviderError::HeaderNotFound(number.into()))
+    }
 
-        let storage_range = BlockNumberAddress::range(range.clone());
+    /// Get senders of all transactions in the given tx range.
+    ///
+    /// Unlike [`TransactionsProvider::senders_by_tx_range`], exactly one sender is returned for
+    /// each stored transaction in the range. Senders that are missing from
+    /// [tables::TransactionSenders], e.g. because they were pruned, are recovered from their
+    /// transactions in parallel.
+    pub fn senders_by_tx_range_recovered(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<Address>> {
+        let transactions = self.transactions_with_numbers_with_cursor(
+            range,
+            &mut self.tx.cursor_read::<tables::Transactions>()?,
+        )?;
+        let senders = self.senders_of_transactions(
+            &transactions,
+            &mut self.tx.cursor_read::<tables::TransactionSenders>()?,
+            true,
+        )?;
 
-        let storage_changeset =
-            self.get_or_take::<tables::StorageChangeSets, UNWIND>(storage_range)?;
-        let account_changeset = self.get_or_take::<tables::AccountChangeSets, UNWIND>(range)?;
+        transactions
+            .iter()
+            .zip(senders)
+            .map(|((tx_num, _), sender)| {
+                sender.ok_or(ProviderError::TransactionSenderRecoveryError(*tx_num))
+            })
+            .collect()
+    }
 
-        // iterate previous value and get plain state value to create changeset
-        // Double option around Account represent if Account state is know (first option) and
-        // account is removed (Second Option)
+    /// Returns the transactions of the given block together with their senders.
+    ///
+    /// Senders are read from [tables::TransactionSenders] and recovered from the transaction if
+    /// missing, e.g. because they were pruned. Returns `None` if the block or its body indices
+    /// are not found, same as [`TransactionsProvider::transactions_by_block`].
+    pub fn transactions_with_senders_by_block(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<TransactionSignedEcRecovered>>> {
+        let Some(block_number) = self.convert_hash_or_number(id)? else { return Ok(None) };
+        let Some(body) = self.block_body_indices(block_number)? else { return Ok(None) };
 
-        let mut state: BundleStateInit = HashMap::new();
+        self.transactions_with_senders_with_cursors(
+            body.tx_num_range(),
+            &mut self.tx.cursor_read::<tables::Transactions>()?,
+            &mut self.tx.cursor_read::<tables::TransactionSenders>()?,
+        )
+        .map(Some)
+    }
 
-        // This is not working for blocks that are not at tip. as plain state is not the last
-        // state of end range. We should rename the functions or add support to access
-        // History state. Accessing history state can be tricky but we are not gaining
-        // anything.
-        let mut plain_accounts_cursor = self.tx.cursor_write::<tables::PlainAccountState>()?;
-        let mut plain_storage_cursor = self.tx.cursor_dup_write::<tables::PlainStorageState>()?;
+    /// Returns the header and the transactions with senders of every block in the given range,
+    /// which is everything needed to set up the EVM and replay the blocks, e.g. for tracing.
+    ///
+    /// Senders are read or recovered the same way as in
+    /// [`DatabaseProvider::transactions_with_senders_by_block`]. The cursors are shared across all
+    /// blocks of the range. Empty blocks are returned with an empty transaction list.
+    pub fn transactions_for_tracing(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Header, Vec<TransactionSignedEcRecovered>)>> {
+        let headers = self.headers_range(range)?;
+        if headers.is_empty() {
+            return Ok(Vec::new())
+        }
 
-        let mut reverts: RevertsInit = HashMap::new();
+        let mut body_cursor = self.tx.cursor_read::<tables::BlockBodyIndices>()?;
+        let mut tx_cursor = self.tx.cursor_read::<tables::Transactions>()?;
+        let mut senders_cursor = self.tx.cursor_read::<tables::TransactionSenders>()?;
 
-        // add account changeset changes
-        for (block_number, account_before) in account_changeset.into_iter().rev() {
-            let AccountBeforeTx { info: old_info, address } = account_before;
-            match state.entry(address) {
-                hash_map::Entry::Vacant(entry) => {
-                    let new_info = plain_accounts_cursor.seek_exact(address)?.map(|kv| kv.1);
-                    entry.insert((old_info, new_info, HashMap::new()));
-                }
-                hash_map::Entry::Occupied(mut entry) => {
-                    // overwrite old account state.
-                    entry.get_mut().0 = old_info;
-                }
-            }
-            // insert old info into reverts.
-            reverts.entry(block_number).or_default().entry(address).or_default().0 = Some(old_info);
+        let mut blocks = Vec::with_capacity(headers.len());
+        for header in headers {
+            let block_number = header.number;
+            let (_, body) = body_cursor
+                .seek_exact(block_number)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
+            let transactions = self.transactions_with_senders_with_cursors(
+                body.tx_num_range(),
+                &mut tx_cursor,
+                &mut senders_cursor,
+            )?;
+            blocks.push((block_number, header, transactions));
         }
 
-        // add storage changeset changes
-        for (block_and_address, old_storage) in storage_changeset.into_iter().rev() {
-            let BlockNumberAddress((block_number, address)) = block_and_address;
-            // get account state or insert from plain state.
-            let account_state = match state.entry(address) {
-                hash_map::Entry::Vacant(entry) => {
-                    let present_info = plain_accounts_cursor.seek_exact(address)?.map(|kv| kv.1);
-                    entry.insert((present_info, present_info, HashMap::new()))
-                }
-                hash_map::Entry::Occupied(entry) => entry.into_mut(),
-            };
-
-            // match storage.
-            match account_state.2.entry(old_storage.key) {
-                hash_map::Entry::Vacant(entry) => {
-                    let new_storage = plain_storage_cursor
-                        .seek_by_key_subkey(address, old_storage.key)?
-                        .filter(|storage| storage.key == old_storage.key)
-                        .unwrap_or_default();
-                    entry.insert((old_storage.value, new_storage.value));
-                }
-                hash_map::Entry::Occupied(mut entry) => {
-                    entry.get_mut().0 = old_storage.value;
-                }
-            };
+        Ok(blocks)
+    }
 
-            reverts
-                .entry(block_number)
-                .or_default()
-                .entry(address)
-                .or_default()
-                .1
-                .push(old_storage);
+    /// Returns the transactions in the given range together with their senders, using the given
+    /// cursors.
+    ///
+    /// Senders are read from [tables::TransactionSenders] and recovered from the transaction if
+    /// missing, e.g. because they were pruned.
+    fn transactions_with_senders_with_cursors(
+        &self,
+        tx_range: Range<TxNumber>,
+        tx_cursor: &mut impl DbCursorRO<tables::Transactions>,
+        senders_cursor: &mut impl DbCursorRO<tables::TransactionSenders>,
+    ) -> ProviderResult<Vec<TransactionSignedEcRecovered>> {
+        if tx_range.is_empty() {
+            return Ok(Vec::new())
         }
 
-        if UNWIND {
-            // iterate over local plain state remove all account and all storages.
-            for (address, (old_account, new_account, storage)) in state.iter() {
-                // revert account if needed.
-                if old_account != new_account {
-                    let existing_entry = plain_accounts_cursor.seek_exact(*address)?;
-                    if let Some(account) = old_account {
-                        plain_accounts_cursor.upsert(*address, *account)?;
-                    } else if existing_entry.is_some() {
-                        plain_accounts_cursor.delete_current()?;
-                    }
-                }
+        let transactions = self.transactions_with_numbers_with_cursor(tx_range, tx_cursor)?;
+        let senders = self.senders_of_transactions(&transactions, senders_cursor, true)?;
 
-                // revert storages
-                for (storage_key, (old_storage_value, _new_storage_value)) in storage {
-                    let storage_entry =
-                        StorageEntry { key: *storage_key, value: *old_storage_value };
-                    // delete previous value
-                    // TODO: This does not use dupsort features
-                    if plain_storage_cursor
-                        .seek_by_key_subkey(*address, *storage_key)?
-                        .filter(|s| s.key == *storage_key)
-                        .is_some()
-                    {
-                        plain_storage_cursor.delete_current()?
-                    }
+        transactions
+            .into_iter()
+            .zip(senders)
+            .map(|((tx_num, transaction), sender)| {
+                let sender = sender.ok_or(ProviderError::TransactionSenderRecoveryError(tx_num))?;
+                Ok(TransactionSignedEcRecovered::from_signed_transaction(
+                    transaction.with_hash(),
+                    sender,
+                ))
+            })
+            .collect()
+    }
 
-                    // insert value if needed
-                    if *old_storage_value != U256::ZERO {
-                        plain_storage_cursor.upsert(*address, storage_entry)?;
+    /// Returns the headers in the given range while `predicate` returns `true`.
+    ///
+    /// Unlike [`HeaderProvider::sealed_headers_while`], the headers are not sealed, so their
+    /// hashes are neither read nor computed.
+    pub fn headers_while(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        predicate: impl FnMut(&Header) -> bool,
+    ) -> ProviderResult<Vec<Header>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::Headers,
+            to_range(range),
+            |static_file, range, predicate| static_file.headers_while(range, predicate),
+            |range, mut predicate| {
+                let mut headers = vec![];
+                for entry in self.tx.cursor_read::<tables::Headers>()?.walk_range(range)? {
+                    let (_, header) = entry?;
+                    if !predicate(&header) {
+                        break
                     }
+                    headers.push(header);
                 }
+                Ok(headers)
+            },
+            predicate,
+        )
+    }
+
+    /// Returns the block number of each transaction in the given range, like
+    /// [`TransactionsProvider::transaction_block`] for a range of transactions.
+    ///
+    /// [tables::TransactionBlocks] is keyed only by the last transaction number of each non-empty
+    /// block, so a transaction number that isn't a key belongs to the block of the next higher
+    /// key. Transactions following the last key aren't part of any known block and are not
+    /// returned.
+    pub fn transaction_blocks_range(
+        &self,
+        tx_range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<(TxNumber, BlockNumber)>> {
+        let tx_range = to_range(tx_range);
+        let mut tx_blocks = Vec::new();
+        if tx_range.is_empty() {
+            return Ok(tx_blocks)
+        }
+
+        let mut next_tx_num = tx_range.start;
+        for entry in self.tx.cursor_read::<tables::TransactionBlocks>()?.walk(Some(next_tx_num))? {
+            let (last_tx_num, block_number) = entry?;
+            let end = last_tx_num.saturating_add(1).min(tx_range.end);
+            tx_blocks.extend((next_tx_num..end).map(|tx_num| (tx_num, block_number)));
+            next_tx_num = end;
+
+            if next_tx_num >= tx_range.end {
+                break
             }
         }
 
-        // iterate over block body and create ExecutionResult
-        let mut receipt_iter = self
-            .get_or_take::<tables::Receipts, UNWIND>(from_transaction_num..=to_transaction_num)?
-            .into_iter();
+        Ok(tx_blocks)
+    }
 
-        let mut receipts = Vec::new();
+    /// Returns the [tables::AccountsHistory] shards of the given address as pairs of the
+    /// highest block number of the shard and the block numbers it contains, in ascending order.
+    ///
+    /// Returns an empty vector if the address has no history shards.
+    pub fn account_history_shards(
+        &self,
+        address: Address,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<BlockNumber>)>> {
+        self.history_shards::<tables::AccountsHistory, _>(ShardedKey::new(address, 0), |key| {
+            key.key == address
+        })
+    }
+
+    /// Returns the [tables::StoragesHistory] shards of the given address and storage slot, like
+    /// [`DatabaseProvider::account_history_shards`].
+    pub fn storage_history_shards(
+        &self,
+        address: Address,
+        storage_key: B256,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<BlockNumber>)>> {
+        self.history_shards::<tables::StoragesHistory, _>(
+            StorageShardedKey::new(address, storage_key, 0),
+            |key| key.address == address && key.sharded_key.key == storage_key,
+        )
+    }
+
+    /// Walks the history table from `start_key` while `key_matches` returns `true`, collecting the
+    /// shards.
+    fn history_shards<T, SK>(
+        &self,
+        start_key: T::Key,
+        key_matches: impl Fn(&T::Key) -> bool,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<BlockNumber>)>>
+    where
+        T: Table<Value = BlockNumberList>,
+        T::Key: AsRef<ShardedKey<SK>>,
+    {
+        let mut shards = Vec::new();
+        for entry in self.tx.cursor_read::<T>()?.walk(Some(start_key))? {
+            let (key, blocks) = entry?;
+            if !key_matches(&key) {
+                break
+            }
+            shards.push((key.as_ref().highest_block_number, blocks.iter().collect()));
+        }
+        Ok(shards)
+    }
+
+    /// Checks that the genesis block stored in the database or static files matches the genesis
+    /// of the configured [ChainSpec].
+    ///
+    /// Both the stored canonical hash of block `0` and the hash of the stored genesis header are
+    /// compared against [`ChainSpec::genesis_hash`], so that a mismatch in any header field is
+    /// detected.
+    pub fn validate_genesis(&self) -> ProviderResult<()> {
+        let expected = self.chain_spec.genesis_hash();
+
+        let stored_hash = match self.block_hash(0) {
+            Ok(hash) => hash,
+            Err(ProviderError::MissingStaticFileBlock(StaticFileSegment::Headers, 0)) => None,
+            Err(err) => return Err(err),
+        }
+        .ok_or(ProviderError::HeaderNotFound(0u64.into()))?;
+        if stored_hash != expected {
+            return Err(ProviderError::GenesisHashMismatch(GotExpected {
+                got: stored_hash,
+                expected,
+            }))
+        }
+
+        let header_hash = self
+            .header_by_number(0)?
+            .ok_or(ProviderError::HeaderNotFound(0u64.into()))?
+            .hash_slow();
+        if header_hash != expected {
+            return Err(ProviderError::GenesisHeaderMismatch(GotExpected {
+                got: header_hash,
+                expected,
+            }))
+        }
+
+        Ok(())
+    }
+
+    /// Returns the lowest block number for which the data of all prune segments is fully
+    /// available, i.e. the block following the highest pruned block across all
+    /// [PruneCheckpoint]s.
+    ///
+    /// Returns `0` if nothing was pruned.
+    pub fn earliest_available_block(&self) -> ProviderResult<BlockNumber> {
+        Ok(self
+            .get_all_prune_checkpoints()?
+            .into_iter()
+            .filter_map(|(_, checkpoint)| checkpoint.block_number)
+            .max()
+            .map_or(0, |block_number| block_number + 1))
+    }
+
+    /// Returns the transaction at position `index` in the given block, without reading the rest
+    /// of the block body.
+    ///
+    /// Returns `None` if the block is unknown or `index` is out of range.
+    pub fn transaction_by_block_and_index(
+        &self,
+        id: BlockHashOrNumber,
+        index: u64,
+    ) -> ProviderResult<Option<TransactionSigned>> {
+        let Some(tx_num) = self.block_tx_num(id, index)? else { return Ok(None) };
+        self.transaction_by_id(tx_num)
+    }
+
+    /// Returns the receipt of the transaction at position `index` in the given block, without
+    /// reading the rest of the block's receipts.
+    ///
+    /// Returns `None` if the block is unknown, `index` is out of range or the receipt was pruned.
+    pub fn receipt_by_block_and_index(
+        &self,
+        id: BlockHashOrNumber,
+        index: u64,
+    ) -> ProviderResult<Option<Receipt>> {
+        let Some(tx_num) = self.block_tx_num(id, index)? else { return Ok(None) };
+        self.receipt(tx_num)
+    }
+
+    /// Computes the receipts root of the given block from its stored receipts.
+    ///
+    /// Returns the empty root for blocks without transactions, `None` if the block is unknown and
+    /// [ProviderError::BlockReceiptsPruned] if any of the block's receipts are missing.
+    pub fn receipts_root_by_block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<B256>> {
+        let Some(block_number) = self.convert_hash_or_number(id)? else { return Ok(None) };
+        let Some(body) = self.block_body_indices(block_number)? else { return Ok(None) };
+        let Some(receipts) = self.receipts_by_block(block_number.into())? else { return Ok(None) };
+        if receipts.len() as u64 != body.tx_count {
+            return Err(ProviderError::BlockReceiptsPruned(block_number))
+        }
+
+        let receipts = receipts.iter().collect::<Vec<_>>();
+
+        #[cfg(feature = "optimism")]
+        {
+            let timestamp = self
+                .header_by_number(block_number)?
+                .ok_or(ProviderError::HeaderNotFound(block_number.into()))?
+                .timestamp;
+            Ok(Some(proofs::calculate_receipt_root_ref_optimism(
+                &receipts,
+                &self.chain_spec,
+                timestamp,
+            )))
+        }
+        #[cfg(not(feature = "optimism"))]
+        Ok(Some(proofs::calculate_receipt_root_ref(&receipts)))
+    }
+
+    /// Returns the number of the transaction at position `index` in the given block, or `None` if
+    /// the block is unknown or `index` is out of range.
+    fn block_tx_num(&self, id: BlockHashOrNumber, index: u64) -> ProviderResult<Option<TxNumber>> {
+        let Some(block_number) = self.convert_hash_or_number(id)? else { return Ok(None) };
+        let Some(body) = self.block_body_indices(block_number)? else { return Ok(None) };
+        Ok((index < body.tx_count).then(|| body.first_tx_num + index))
+    }
+
+    /// Get transactions with their [TransactionMeta] by hashes, same as
+    /// [`TransactionsProvider::transaction_by_hash_with_meta`] for each hash.
+    ///
+    /// The header and body indices of each block are read only once, no matter how many of the
+    /// requested transactions belong to it. The output matches the input order, with `None` for
+    /// unknown transactions.
+    pub fn transactions_by_hashes_with_meta(
+        &self,
+        hashes: impl IntoIterator<Item = TxHash>,
+    ) -> ProviderResult<Vec<Option<(TransactionSigned, TransactionMeta)>>> {
+        let mut transaction_cursor = self.tx.cursor_read::<tables::TransactionBlocks>()?;
+        let mut blocks: HashMap<BlockNumber, Option<(SealedHeader, StoredBlockBodyIndices)>> =
+            HashMap::new();
+
+        let mut transaction_with_meta = |tx_hash: TxHash| -> ProviderResult<Option<_>> {
+            let Some(transaction_id) = self.transaction_id(tx_hash)? else { return Ok(None) };
+            let Some(tx) = self.transaction_by_id_no_hash(transaction_id)? else { return Ok(None) };
+            let Some((_, block_number)) = transaction_cursor.seek(transaction_id)? else {
+                return Ok(None)
+            };
+
+            let block = match blocks.entry(block_number) {
+                hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                hash_map::Entry::Vacant(entry) => entry.insert(
+                    self.sealed_header(block_number)?.zip(self.block_body_indices(block_number)?),
+                ),
+            };
+            let Some((header, block_body)) = block else { return Ok(None) };
+
+            let transaction = TransactionSigned {
+                hash: tx_hash,
+                signature: tx.signature,
+                transaction: tx.transaction,
+            };
+            let meta = TransactionMeta {
+                tx_hash,
+                index: transaction_id - block_body.first_tx_num(),
+                block_hash: header.hash(),
+                block_number,
+                base_fee: header.base_fee_per_gas,
+                excess_blob_gas: header.excess_blob_gas,
+                timestamp: header.timestamp,
+            };
+            Ok(Some((transaction, meta)))
+        };
+
+        hashes.into_iter().map(transaction_with_meta).collect()
+    }
+
+    /// Returns the receipts of the given block together with the block's logs bloom, computed
+    /// from the logs of all receipts.
+    ///
+    /// Returns `None` under the same conditions as [`ReceiptProvider::receipts_by_block`].
+    pub fn receipts_by_block_with_bloom(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<(Vec<Receipt>, Bloom)>> {
+        Ok(self.receipts_by_block(block)?.map(|receipts| {
+            let bloom = logs_bloom(receipts.iter().flat_map(|receipt| &receipt.logs));
+            (receipts, bloom)
+        }))
+    }
+
+    /// Get the range of transaction numbers of all blocks in the given block range.
+    ///
+    /// Only the body indices of the first and last block are read, since transaction numbers are
+    /// contiguous across blocks.
+    ///
+    /// Returns `None` if all blocks in the range are empty.
+    pub fn tx_range_for_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<Range<TxNumber>>> {
+        if range.is_empty() {
+            return Ok(None)
+        }
+
+        let (first_block, last_block) = range.into_inner();
+        let first_body = self
+            .block_body_indices(first_block)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(first_block))?;
+        let last_body = self
+            .block_body_indices(last_block)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(last_block))?;
+
+        Ok(block_bodies_tx_range(&first_body, &last_body))
+    }
+
+    /// Get the header of the last block and its hash, without resealing the header.
+    ///
+    /// Returns `None` if the database has no blocks.
+    pub fn latest_header_and_hash(&self) -> ProviderResult<Option<(Header, B256)>> {
+        let number = self.last_block_number()?;
+        Ok(self.header_by_number(number)?.zip(self.block_hash(number)?))
+    }
+
+    /// Walk [tables::AccountChangeSets] in the given block range and call `f` with the block
+    /// number and the account state before the change, for every changeset entry.
+    ///
+    /// Entries are visited in table order, i.e. by ascending block number and then address.
+    pub fn for_each_account_change(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        mut f: impl FnMut(BlockNumber, AccountBeforeTx),
+    ) -> ProviderResult<()> {
+        // Cursors can only seek to an inclusive start key, so an excluded start bound (e.g. a
+        // `(Bound::Excluded, _)` tuple) is converted into the next included one.
+        let start = match range.start_bound().cloned() {
+            Bound::Excluded(start) => match start.checked_add(1) {
+                Some(start) => Bound::Included(start),
+                None => return Ok(()),
+            },
+            bound => bound,
+        };
+
+        let mut cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
+        for entry in cursor.walk_range((start, range.end_bound().cloned()))? {
+            let (block_number, account_before) = entry?;
+            f(block_number, account_before);
+        }
+        Ok(())
+    }
+
+    /// Walk [tables::StorageChangeSets] in the given range and call `f` with the key and the
+    /// storage entry before the change, for every changeset entry.
+    ///
+    /// Entries are visited in table order, i.e. by ascending block number, then address and then
+    /// storage key.
+    pub fn for_each_storage_change(
+        &self,
+        range: impl RangeBounds<BlockNumberAddress>,
+        mut f: impl FnMut(BlockNumberAddress, StorageEntry),
+    ) -> ProviderResult<()> {
+        let mut cursor = self.tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+
+        // Cursors can only seek to an inclusive start key, so an excluded start bound is converted
+        // into the key following all entries of the excluded one.
+        let start = match range.start_bound().cloned() {
+            Bound::Excluded(start) => {
+                let next = match cursor.seek(start)? {
+                    Some((key, _)) if key == start => cursor.next_no_dup()?,
+                    next => next,
+                };
+                match next {
+                    Some((key, _)) => Bound::Included(key),
+                    None => return Ok(()),
+                }
+            }
+            bound => bound,
+        };
+
+        for entry in cursor.walk_range((start, range.end_bound().cloned()))? {
+            let (block_number_address, storage_entry) = entry?;
+            f(block_number_address, storage_entry);
+        }
+        Ok(())
+    }
+
+    /// Get the accounts changed in the given block range, together with their value before the
+    /// first change in the range and their latest value from [tables::PlainAccountState].
+    ///
+    /// Accounts created in the range have no before value, and accounts that don't exist anymore
+    /// have no latest value. Note that the latest value is read from the plain state, so it
+    /// reflects the changes of all blocks up to the tip, not only those up to the end of the range.
+    pub fn account_diffs_in_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, (Option<Account>, Option<Account>)>> {
+        let mut accounts_before = BTreeMap::new();
+        // Changesets are walked in ascending block order, so the first entry of each account holds
+        // its value before the range.
+        self.for_each_account_change(range, |_, account_before| {
+            accounts_before.entry(account_before.address).or_insert(account_before.info);
+        })?;
+
+        let mut plain_accounts_cursor = self.tx.cursor_read::<tables::PlainAccountState>()?;
+        accounts_before
+            .into_iter()
+            .map(|(address, before)| {
+                let after = plain_accounts_cursor.seek_exact(address)?.map(|(_, account)| account);
+                Ok((address, (before, after)))
+            })
+            .collect()
+    }
+
+    /// Get the storage changeset of the given block, i.e. the value of every storage slot before
+    /// it was changed by the block, together with the address of the account it belongs to.
+    ///
+    /// This is the storage analog of [`ChangeSetReader::account_block_changeset`]. Entries are
+    /// sorted by address and then by storage key.
+    pub fn storage_changesets_by_block(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        let mut changeset = Vec::new();
+        self.for_each_storage_change(
+            BlockNumberAddress::range(block_number..=block_number),
+            |key, storage_entry| changeset.push((key.address(), storage_entry)),
+        )?;
+        Ok(changeset)
+    }
+
+    /// Get the number of the last finalized block, as persisted by
+    /// [`DatabaseProvider::save_finalized_block_number`].
+    ///
+    /// Returns `None` if no finalized block was saved, or if the saved block was unwound since.
+    pub fn finalized_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ChainState>(tables::ChainStateKey::LastFinalizedBlock)?)
+    }
+
+    /// Get the canonical hashes of the blocks in the given range.
+    ///
+    /// Unlike [`BlockHashReader::canonical_hashes_range`], which takes an exclusive `start..end`
+    /// pair, this accepts any range bounds, consistent with [`HeaderProvider::headers_range`]. An
+    /// unbounded end is capped at the last block number.
+    pub fn canonical_hashes_with_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<B256>> {
+        let range = match range.end_bound() {
+            Bound::Unbounded => {
+                to_range((range.start_bound().cloned(), Bound::Included(self.last_block_number()?)))
+            }
+            _ => to_range(range),
+        };
+        self.canonical_hashes_range(range.start, range.end)
+    }
+
+    /// Get the latest nonce of the account, or `None` if the account doesn't exist.
+    pub fn account_nonce(&self, address: Address) -> ProviderResult<Option<u64>> {
+        Ok(self.basic_account(address)?.map(|account| account.nonce))
+    }
+
+    /// Get the latest balance of the account, or `None` if the account doesn't exist.
+    pub fn account_balance(&self, address: Address) -> ProviderResult<Option<U256>> {
+        Ok(self.basic_account(address)?.map(|account| account.balance))
+    }
+
+    /// Get the best block number, falling back to the last block number if the
+    /// [StageId::Finish] checkpoint is missing.
+    ///
+    /// Databases populated without running the pipeline, e.g. with `insert_historical_block`, don't
+    /// have the [StageId::Finish] checkpoint set, so [`BlockNumReader::best_block_number`] would
+    /// report them as empty.
+    pub fn resolved_best_block_number(&self) -> ProviderResult<BlockNumber> {
+        if let Some(checkpoint) = self.get_stage_checkpoint(StageId::Finish)? {
+            return Ok(checkpoint.block_number)
+        }
+
+        let last_block_number = self.last_block_number()?;
+        debug!(
+            target: "providers::db",
+            %last_block_number,
+            "Finish stage checkpoint not found, falling back to last block number"
+        );
+        Ok(last_block_number)
+    }
+
+    /// Get the sealed header of the fully synced tip, i.e. the block at
+    /// [`BlockNumReader::best_block_number`] as recorded by the [StageId::Finish] checkpoint.
+    ///
+    /// This differs from [`HeaderProvider::latest_sealed_header`], which returns the last
+    /// downloaded header. While the pipeline is running, headers can be downloaded well ahead of
+    /// the blocks that were executed, so fork choice and the `safe`/`finalized` tags should use
+    /// this method instead.
+    pub fn finished_head_header(&self) -> ProviderResult<Option<SealedHeader>> {
+        self.sealed_header(self.best_block_number()?)
+    }
+
+    /// Returns whether the canonical block exists, i.e. both its header and its body indices are
+    /// stored.
+    ///
+    /// The header is checked through its canonical hash in the database or static files, so
+    /// neither the header nor the block body are decoded. For a hash, the canonical hash at its
+    /// height has to match it, so `false` is returned for blocks that were reorged out. A header
+    /// without body indices is not considered a usable block, so `false` is returned for it.
+    pub fn block_exists(&self, id: BlockHashOrNumber) -> ProviderResult<bool> {
+        let Some(number) = self.convert_hash_or_number(id)? else { return Ok(false) };
+        let header_exists = match (id, self.block_hash(number)?) {
+            (_, None) => false,
+            (BlockHashOrNumber::Hash(hash), Some(canonical_hash)) => hash == canonical_hash,
+            (BlockHashOrNumber::Number(_), Some(_)) => true,
+        };
+        if !header_exists {
+            return Ok(false)
+        }
+        Ok(self.tx.get::<tables::BlockBodyIndices>(number)?.is_some())
+    }
+
+    /// Returns whether the block with the given hash is part of the canonical chain.
+    ///
+    /// Blocks that are known but were reorged out, as well as unknown blocks, are not canonical.
+    pub fn is_block_canonical(&self, hash: B256) -> ProviderResult<bool> {
+        let Some(number) = self.block_number(hash)? else { return Ok(false) };
+        Ok(self.block_hash(number)? == Some(hash))
+    }
+
+    /// Get the block numbers of the given block hashes, reusing a single cursor.
+    ///
+    /// The output matches the input order, with `None` for unknown hashes.
+    pub fn block_numbers(
+        &self,
+        hashes: impl IntoIterator<Item = B256>,
+    ) -> ProviderResult<Vec<Option<BlockNumber>>> {
+        let mut cursor = self.tx.cursor_read::<tables::HeaderNumbers>()?;
+        hashes
+            .into_iter()
+            .map(|hash| Ok(cursor.seek_exact(hash)?.map(|(_, number)| number)))
+            .collect()
+    }
+
+    /// Get all accounts from [tables::PlainAccountState] within the given address range.
+    pub fn plain_accounts_range(
+        &self,
+        range: impl RangeBounds<Address>,
+    ) -> ProviderResult<Vec<(Address, Account)>> {
+        // Cursors can only seek to an inclusive start key, so with an excluded start bound the
+        // walk starts at the bound itself and the matching account is skipped.
+        let excluded_start = match range.start_bound() {
+            Bound::Excluded(start) => Some(*start),
+            _ => None,
+        };
+        let start = match range.start_bound().cloned() {
+            Bound::Excluded(start) => Bound::Included(start),
+            bound => bound,
+        };
+
+        let mut accounts = Vec::new();
+        for entry in self
+            .tx
+            .cursor_read::<tables::PlainAccountState>()?
+            .walk_range((start, range.end_bound().cloned()))?
+        {
+            let (address, account) = entry?;
+            if Some(address) != excluded_start {
+                accounts.push((address, account));
+            }
+        }
+        Ok(accounts)
+    }
+
+    /// Verify the storage roots of all accounts within the given address range.
+    ///
+    /// For each account, the storage root computed from [tables::PlainStorageState] is compared
+    /// against the root computed through the trie from [tables::HashedStorages], as in
+    /// [`HashingWriter::storage_root_for_account`]. Returns the accounts with mismatching roots,
+    /// where `got` is the root from the trie and `expected` the one computed from plain state.
+    pub fn verify_account_storage_roots(
+        &self,
+        address_range: impl RangeBounds<Address>,
+    ) -> ProviderResult<Vec<(Address, GotExpected<B256>)>> {
+        let mut plain_storage_cursor = self.tx.cursor_dup_read::<tables::PlainStorageState>()?;
+
+        let mut mismatches = Vec::new();
+        for (address, _) in self.plain_accounts_range(address_range)? {
+            let storage = plain_storage_cursor
+                .walk_dup(Some(address), None)?
+                .map(|entry| entry.map(|(_, storage)| (storage.key, storage.value)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let expected = proofs::storage_root_unhashed(storage);
+
+            let got = self.hashed_storage_root(keccak256(address))?;
+
+            if got != expected {
+                mismatches.push((address, GotExpected { got, expected }));
+            }
+        }
+        Ok(mismatches)
+    }
+
+    /// Compute the storage root of a single account from [tables::HashedStorages], see
+    /// [`HashingWriter::storage_root_for_account`].
+    ///
+    /// Kept on the read-only provider, so that [`DatabaseProvider::verify_account_storage_roots`]
+    /// doesn't require a write transaction.
+    fn hashed_storage_root(&self, hashed_address: B256) -> ProviderResult<B256> {
+        let mut prefix_set = PrefixSetMut::default();
+        let mut hashed_storage_cursor = self.tx.cursor_dup_read::<tables::HashedStorages>()?;
+        for entry in hashed_storage_cursor.walk_dup(Some(hashed_address), None)? {
+            let (_, storage_entry) = entry?;
+            prefix_set.insert(Nibbles::unpack(storage_entry.key));
+        }
+
+        let root = StorageRoot::from_tx_hashed(&self.tx, hashed_address)
+            .with_prefix_set(prefix_set.freeze())
+            .root()
+            .map_err(Into::<reth_db::DatabaseError>::into)?;
+        Ok(root)
+    }
+
+    /// Get the total number of transactions in the given block range.
+    ///
+    /// Sums the transaction counts from [tables::BlockBodyIndices] with a single walk, without
+    /// reading any transactions. Blocks without body indices don't contribute.
+    pub fn tx_count_in_range(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<u64> {
+        let mut tx_count = 0;
+        for entry in self.tx.cursor_read::<tables::BlockBodyIndices>()?.walk_range(block_range)? {
+            tx_count += entry?.1.tx_count;
+        }
+        Ok(tx_count)
+    }
+
+    /// Get the sealed header and total difficulty of the block with the given hash.
+    ///
+    /// The hash is resolved to a block number only once. Returns `None` if the hash is unknown,
+    /// and [`ProviderError::HeaderNotFound`] if the header exists but its total difficulty doesn't.
+    pub fn sealed_header_and_td_by_hash(
+        &self,
+        hash: BlockHash,
+    ) -> ProviderResult<Option<(SealedHeader, U256)>> {
+        let Some(number) = self.block_number(hash)? else { return Ok(None) };
+        let Some(header) = self.header_by_number(number)? else { return Ok(None) };
+        let td =
+            self.header_td_by_number(number)?.ok_or(ProviderError::HeaderNotFound(hash.into()))?;
+        Ok(Some((header.seal(hash), td)))
+    }
+
+    /// Get headers in the given range paired with their total difficulty.
+    ///
+    /// Headers and total difficulties are read with aligned range walkers, instead of looking up
+    /// the total difficulty of each header separately.
+    ///
+    /// NOTE: Same as [`HeaderProvider::header_td_by_number`], blocks after the final Paris (merge)
+    /// block return the final Paris total difficulty.
+    pub fn headers_with_td_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(Header, U256)>> {
+        let headers = self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::Headers,
+            to_range(range),
+            |static_file, range, _| static_file.headers_with_td_range(range),
+            |range, _| {
+                let mut headers_cursor = self.tx.cursor_read::<tables::Headers>()?;
+                let mut td_cursor = self.tx.cursor_read::<tables::HeaderTerminalDifficulties>()?;
+                let mut td_walker = td_cursor.walk_range(range.clone())?;
+
+                // The range end may be unbounded, so the result isn't pre-allocated.
+                headers_cursor
+                    .walk_range(range)?
+                    .map(|entry| {
+                        let (number, header) = entry?;
+                        let td = match td_walker.next().transpose()? {
+                            Some((td_number, td)) if td_number == number => td.0,
+                            _ => return Err(ProviderError::TotalDifficultyNotFound(number)),
+                        };
+                        Ok((header, td))
+                    })
+                    .collect()
+            },
+            |_| true,
+        )?;
+
+        Ok(headers
+            .into_iter()
+            .map(|(header, td)| {
+                let td = self.chain_spec.final_paris_total_difficulty(header.number).unwrap_or(td);
+                (header, td)
+            })
+            .collect())
+    }
+}
+
+impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
+    /// Commit database transaction.
+    pub fn commit(self) -> ProviderResult<bool> {
+        Ok(self.tx.commit()?)
+    }
+
+    /// Persist the number of the last finalized block, so that it survives restarts.
+    ///
+    /// The value is cleared once the finalized block gets unwound, see
+    /// [`DatabaseProvider::finalized_block_number`].
+    pub fn save_finalized_block_number(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        Ok(self
+            .tx
+            .put::<tables::ChainState>(tables::ChainStateKey::LastFinalizedBlock, block_number)?)
+    }
+
+    /// Appends a batch of sealed blocks together with their state, changesets and history
+    /// indices, like [`BlockWriter::append_blocks_with_state`], but without writing the hashed
+    /// state and trie updates.
+    ///
+    /// This allows a fast bulk import followed by a single merkle pass. The hashing and merkle
+    /// stages, as well as [StageId::Finish], are left at their current checkpoints, so that the
+    /// pipeline recomputes the hashed state and the trie on its next run. Until then, the hashed
+    /// state and the trie are inconsistent with the plain state.
+    pub fn append_blocks_with_state_no_trie(
+        &self,
+        blocks: Vec<SealedBlockWithSenders>,
+        state: BundleStateWithReceipts,
+        prune_modes: Option<&PruneModes>,
+    ) -> ProviderResult<()> {
+        let mut durations_recorder = metrics::DurationsRecorder::default();
+
+        let Some(range) =
+            self.append_blocks_and_state(blocks, state, prune_modes, &mut durations_recorder)?
+        else {
+            return Ok(())
+        };
+
+        // Update pipeline progress of all stages that don't depend on the hashed state or trie
+        for stage_id in StageId::ALL {
+            if matches!(
+                stage_id,
+                StageId::MerkleUnwind |
+                    StageId::AccountHashing |
+                    StageId::StorageHashing |
+                    StageId::MerkleExecute |
+                    StageId::Finish
+            ) {
+                continue
+            }
+
+            let checkpoint = self.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+            self.save_stage_checkpoint(
+                stage_id,
+                StageCheckpoint { block_number: *range.end(), ..checkpoint },
+            )?;
+        }
+        durations_recorder.record_relative(metrics::Action::UpdatePipelineStages);
+
+        debug!(target: "providers::db", ?range, actions = ?durations_recorder.actions, "Appended blocks without trie updates");
+
+        Ok(())
+    }
+
+    /// Inserts the blocks, writes their state and changesets and updates the history indices.
+    ///
+    /// Returns the range of inserted blocks, or `None` if there are no blocks.
+    fn append_blocks_and_state(
+        &self,
+        blocks: Vec<SealedBlockWithSenders>,
+        state: BundleStateWithReceipts,
+        prune_modes: Option<&PruneModes>,
+        durations_recorder: &mut metrics::DurationsRecorder,
+    ) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        if blocks.is_empty() {
+            debug!(target: "providers::db", "Attempted to append empty block range");
+            return Ok(None)
+        }
+
+        let first_number = blocks.first().unwrap().number;
+        let last_block_number = blocks.last().unwrap().number;
+
+        // Insert the blocks
+        for block in blocks {
+            self.insert_block(block, prune_modes)?;
+            durations_recorder.record_relative(metrics::Action::InsertBlock);
+        }
+
+        // Write state and changesets to the database.
+        // Must be written after blocks because of the receipt lookup.
+        state.write_to_storage(self.tx_ref(), None, OriginalValuesKnown::No)?;
+        durations_recorder.record_relative(metrics::Action::InsertState);
+
+        self.update_history_indices(first_number..=last_block_number)?;
+        durations_recorder.record_relative(metrics::Action::InsertHistoryIndices);
+
+        Ok(Some(first_number..=last_block_number))
+    }
+
+    /// Reset the checkpoint of a single stage back to block zero and clear its
+    /// [tables::StageCheckpointProgresses] entry, so that the stage is re-run from scratch by the
+    /// next pipeline run. Checkpoints of other stages are not touched.
+    ///
+    /// If the stage has no checkpoint stored yet, a zero checkpoint is written. A missing progress
+    /// entry is not an error.
+    pub fn reset_stage_checkpoint(&self, id: StageId) -> ProviderResult<()> {
+        self.save_stage_checkpoint(id, StageCheckpoint::default())?;
+        self.tx.delete::<tables::StageCheckpointProgresses>(id.to_string(), None)?;
+        Ok(())
+    }
+
+    /// Unwind last N blocks of state recreating the [`BundleStateWithReceipts`].
+    ///
+    /// The tip and latest state are unwound and returned back with all the blocks. See
+    /// [`Self::peek_state`] for a read-only variant.
+    pub fn unwind_state(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BundleStateWithReceipts> {
+        self.unwind_or_peek_state::<true>(range)
+    }
+
+    /// Peek at last N blocks of state recreating the [`BundleStateWithReceipts`].
+    ///
+    /// Unlike [`Self::unwind_state`], the state/blocks are only read and left in place.
+    pub fn peek_state(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BundleStateWithReceipts> {
+        self.unwind_or_peek_state::<false>(range)
+    }
+
+    // TODO(joshie) TEMPORARY should be moved to trait providers
+    /// Unwind or peek at last N blocks of state recreating the [`BundleStateWithReceipts`].
+    ///
+    /// If UNWIND it set to true tip and latest state will be unwind
+    /// and returned back with all the blocks
+    ///
+    /// If UNWIND is false we will just read the state/blocks and return them.
+    ///
+    /// 1. Iterate over the [BlockBodyIndices][tables::BlockBodyIndices] table to get all
+    /// the transaction ids.
+    /// 2. Iterate over the [StorageChangeSets][tables::StorageChangeSets] table
+    /// and the [AccountChangeSets][tables::AccountChangeSets] tables in reverse order to
+    /// reconstruct the changesets.
+    ///     - In order to have both the old and new values in the changesets, we also access the
+    ///       plain state tables.
+    /// 3. While iterating over the changeset tables, if we encounter a new account or storage slot,
+    /// we:
+    ///     1. Take the old value from the changeset
+    ///     2. Take the new value from the plain state
+    ///     3. Save the old value to the local state
+    /// 4. While iterating over the changeset tables, if we encounter an account/storage slot we
+    /// have seen before we:
+    ///     1. Take the old value from the changeset
+    ///     2. Take the new value from the local state
+    ///     3. Set the local state to the value in the changeset
+    fn unwind_or_peek_state<const UNWIND: bool>(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BundleStateWithReceipts> {
+        if range.is_empty() {
+            return Ok(BundleStateWithReceipts::default())
+        }
+        let start_block_number = *range.start();
+
+        // We are not removing block meta as it is used to get block changesets.
+        let block_bodies = self.get_or_take::<tables::BlockBodyIndices, false>(range.clone())?;
+
+        // get transaction receipts
+        let tx_range = block_bodies_tx_range(
+            &block_bodies.first().expect("already checked if there are blocks").1,
+            &block_bodies.last().expect("already checked if there are blocks").1,
+        );
+
+        let storage_range = BlockNumberAddress::range(range.clone());
+
+        // Changesets are read newest first, so that the oldest values are applied last.
+        let storage_changeset =
+            self.get_or_take_rev::<tables::StorageChangeSets, UNWIND>(storage_range)?;
+        let account_changeset = self.get_or_take_rev::<tables::AccountChangeSets, UNWIND>(range)?;
+
+        // iterate previous value and get plain state value to create changeset
+        // Double option around Account represent if Account state is know (first option) and
+        // account is removed (Second Option)
+
+        let mut state: BundleStateInit = HashMap::new();
+
+        // This is not working for blocks that are not at tip. as plain state is not the last
+        // state of end range. We should rename the functions or add support to access
+        // History state. Accessing history state can be tricky but we are not gaining
+        // anything.
+        let mut plain_accounts_cursor = self.tx.cursor_write::<tables::PlainAccountState>()?;
+        let mut plain_storage_cursor = self.tx.cursor_dup_write::<tables::PlainStorageState>()?;
+
+        let mut reverts: RevertsInit = HashMap::new();
+
+        // add account changeset changes
+        for (block_number, account_before) in account_changeset {
+            let AccountBeforeTx { info: old_info, address } = account_before;
+            match state.entry(address) {
+                hash_map::Entry::Vacant(entry) => {
+                    let new_info = plain_accounts_cursor.seek_exact(address)?.map(|kv| kv.1);
+                    entry.insert((old_info, new_info, HashMap::new()));
+                }
+                hash_map::Entry::Occupied(mut entry) => {
+                    // overwrite old account state.
+                    entry.get_mut().0 = old_info;
+                }
+            }
+            // insert old info into reverts.
+            reverts.entry(block_number).or_default().entry(address).or_default().0 = Some(old_info);
+        }
+
+        // add storage changeset changes
+        for (block_and_address, old_storage) in storage_changeset {
+            let BlockNumberAddress((block_number, address)) = block_and_address;
+            // get account state or insert from plain state.
+            let account_state = match state.entry(address) {
+                hash_map::Entry::Vacant(entry) => {
+                    let present_info = plain_accounts_cursor.seek_exact(address)?.map(|kv| kv.1);
+                    entry.insert((present_info, present_info, HashMap::new()))
+                }
+                hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            };
+
+            // match storage.
+            match account_state.2.entry(old_storage.key) {
+                hash_map::Entry::Vacant(entry) => {
+                    let new_storage = plain_storage_cursor
+                        .seek_by_key_subkey(address, old_storage.key)?
+                        .filter(|storage| storage.key == old_storage.key)
+                        .unwrap_or_default();
+                    entry.insert((old_storage.value, new_storage.value));
+                }
+                hash_map::Entry::Occupied(mut entry) => {
+                    entry.get_mut().0 = old_storage.value;
+                }
+            };
+
+            reverts
+                .entry(block_number)
+                .or_default()
+                .entry(address)
+                .or_default()
+                .1
+                .push(old_storage);
+        }
+
+        if UNWIND {
+            // iterate over local plain state remove all account and all storages.
+            for (address, (old_account, new_account, storage)) in state.iter() {
+                // revert account if needed.
+                if old_account != new_account {
+                    let existing_entry = plain_accounts_cursor.seek_exact(*address)?;
+                    if let Some(account) = old_account {
+                        plain_accounts_cursor.upsert(*address, *account)?;
+                    } else if existing_entry.is_some() {
+                        plain_accounts_cursor.delete_current()?;
+                    }
+                }
+
+                // revert storages
+                for (storage_key, (old_storage_value, _new_storage_value)) in storage {
+                    let storage_entry =
+                        StorageEntry { key: *storage_key, value: *old_storage_value };
+                    // delete previous value
+                    // TODO: This does not use dupsort features
+                    if plain_storage_cursor
+                        .seek_by_key_subkey(*address, *storage_key)?
+                        .filter(|s| s.key == *storage_key)
+                        .is_some()
+                    {
+                        plain_storage_cursor.delete_current()?
+                    }
+
+                    // insert value if needed
+                    if *old_storage_value != U256::ZERO {
+                        plain_storage_cursor.upsert(*address, storage_entry)?;
+                    }
+                }
+            }
+        }
+
+        // iterate over block body and create ExecutionResult
+        let mut receipt_iter = tx_range
+            .map(|tx_range| self.get_or_take::<tables::Receipts, UNWIND>(tx_range))
+            .transpose()?
+            .unwrap_or_default()
+            .into_iter();
+
+        let mut receipts = Vec::new();
         // loop break if we are at the end of the blocks.
         for (_, block_body) in block_bodies.into_iter() {
             let mut block_receipts = Vec::with_capacity(block_body.tx_count as usize);
@@ -561,7 +1689,74 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         }
     }
 
+    /// Return list of entries from table that are not kept by the `keep` predicate.
+    ///
+    /// If TAKE is true, opened cursor would be write and only the entries for which `keep`
+    /// returns `false` are deleted from db and returned. Entries for which `keep` returns `true`
+    /// are left untouched. If TAKE is false, the same entries are returned without deleting
+    /// anything.
+    #[inline]
+    pub fn get_or_take_filtered<T: Table, const TAKE: bool>(
+        &self,
+        range: impl RangeBounds<T::Key>,
+        mut keep: impl FnMut(&KeyValue<T>) -> bool,
+    ) -> Result<Vec<KeyValue<T>>, DatabaseError> {
+        if TAKE {
+            let mut cursor_write = self.tx.cursor_write::<T>()?;
+            let mut walker = cursor_write.walk_range(range)?;
+            let mut items = Vec::new();
+            while let Some(i) = walker.next().transpose()? {
+                if keep(&i) {
+                    continue
+                }
+                walker.delete_current()?;
+                items.push(i)
+            }
+            Ok(items)
+        } else {
+            let mut items = Vec::new();
+            for entry in self.tx.cursor_read::<T>()?.walk_range(range)? {
+                let entry = entry?;
+                if !keep(&entry) {
+                    items.push(entry)
+                }
+            }
+            Ok(items)
+        }
+    }
+
+    /// Return list of entries from table in reverse order, starting from the highest key.
+    ///
+    /// If TAKE is true, opened cursor would be write and it would delete all values from db.
+    #[inline]
+    pub fn get_or_take_rev<T: Table, const TAKE: bool>(
+        &self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<Vec<KeyValue<T>>, DatabaseError> {
+        if TAKE {
+            let mut cursor_write = self.tx.cursor_write::<T>()?;
+            let mut walker = walk_range_back(&mut cursor_write, &range)?;
+            let mut items = Vec::new();
+            while let Some(i) = walker.next().transpose()? {
+                if !range.contains(&i.0) {
+                    break
+                }
+                walker.delete_current()?;
+                items.push(i)
+            }
+            Ok(items)
+        } else {
+            let mut cursor = self.tx.cursor_read::<T>()?;
+            walk_range_back(&mut cursor, &range)?
+                .take_while(|entry| entry.as_ref().map_or(true, |(key, _)| range.contains(key)))
+                .collect::<Result<Vec<_>, _>>()
+        }
+    }
+
     /// Get requested blocks transaction with signer
+    ///
+    /// Blocks are processed in chunks of [`BLOCK_TRANSACTION_RANGE_CHUNK_SIZE`], so that the
+    /// intermediate transaction and sender vectors never span more than one chunk.
     pub(crate) fn get_take_block_transaction_range<const TAKE: bool>(
         &self,
         range: impl RangeBounds<BlockNumber> + Clone,
@@ -569,18 +1764,31 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         // Raad range of block bodies to get all transactions id's of this range.
         let block_bodies = self.get_or_take::<tables::BlockBodyIndices, false>(range)?;
 
-        if block_bodies.is_empty() {
-            return Ok(Vec::new())
+        let mut block_tx = Vec::with_capacity(block_bodies.len());
+        for chunk in block_bodies.chunks(BLOCK_TRANSACTION_RANGE_CHUNK_SIZE) {
+            block_tx.extend(self.get_take_block_transaction_chunk::<TAKE>(chunk)?);
         }
 
-        // Compute the first and last tx ID in the range
-        let first_transaction = block_bodies.first().expect("If we have headers").1.first_tx_num();
-        let last_transaction = block_bodies.last().expect("Not empty").1.last_tx_num();
+        Ok(block_tx)
+    }
 
-        // If this is the case then all of the blocks in the range are empty
-        if last_transaction < first_transaction {
-            return Ok(block_bodies.into_iter().map(|(n, _)| (n, Vec::new())).collect())
-        }
+    /// Get or take the transactions with signers of the given consecutive block bodies.
+    fn get_take_block_transaction_chunk<const TAKE: bool>(
+        &self,
+        block_bodies: &[(BlockNumber, StoredBlockBodyIndices)],
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<TransactionSignedEcRecovered>)>> {
+        let (Some((_, first_body)), Some((_, last_body))) =
+            (block_bodies.first(), block_bodies.last())
+        else {
+            return Ok(Vec::new())
+        };
+
+        // Compute the first and last tx ID in the chunk
+        let Some(tx_range) = block_bodies_tx_range(first_body, last_body) else {
+            // All of the blocks in the chunk are empty
+            return Ok(block_bodies.iter().map(|(n, _)| (*n, Vec::new())).collect())
+        };
+        let (first_transaction, last_transaction) = (tx_range.start, tx_range.end - 1);
 
         // Get transactions and senders
         let transactions = self
@@ -678,7 +1886,7 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         let mut block_tx = Vec::with_capacity(block_bodies.len());
         let mut senders = senders.into_iter();
         let mut transactions = transactions.into_iter();
-        for (block_number, block_body) in block_bodies {
+        for (block_number, block_body) in block_bodies.iter().cloned() {
             let mut one_block_tx = Vec::with_capacity(block_body.tx_count as usize);
             for _ in block_body.tx_num_range() {
                 let tx = transactions.next();
@@ -712,34 +1920,61 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
     ) -> ProviderResult<Vec<SealedBlockWithSenders>> {
         // For block we need Headers, Bodies, Uncles, withdrawals, Transactions, Signers
 
-        let block_headers = self.get_or_take::<tables::Headers, TAKE>(range.clone())?;
-        if block_headers.is_empty() {
-            return Ok(Vec::new())
-        }
+        let block_headers = if TAKE {
+            let block_headers = self.get_or_take::<tables::Headers, TAKE>(range.clone())?;
+            if block_headers.is_empty() {
+                return Ok(Vec::new())
+            }
+            let block_header_hashes =
+                self.get_or_take::<tables::CanonicalHeaders, TAKE>(range.clone())?;
+
+            // rm HeaderTerminalDifficulties
+            self.get_or_take::<tables::HeaderTerminalDifficulties, TAKE>(range.clone())?;
+            // rm HeaderNumbers
+            let mut header_number_cursor = self.tx.cursor_write::<tables::HeaderNumbers>()?;
+            for (_, hash) in block_header_hashes.iter() {
+                if header_number_cursor.seek_exact(*hash)?.is_some() {
+                    header_number_cursor.delete_current()?;
+                }
+            }
+
+            // The persisted finalized block must not point to an unwound block.
+            if self
+                .finalized_block_number()?
+                .is_some_and(|finalized| finalized >= block_headers[0].0)
+            {
+                self.tx.delete::<tables::ChainState>(
+                    tables::ChainStateKey::LastFinalizedBlock,
+                    None,
+                )?;
+            }
+
+            block_headers
+                .into_iter()
+                .zip(block_header_hashes)
+                .map(|((_, header), (_, header_hash))| header.seal(header_hash))
+                .collect()
+        } else {
+            // Read the headers already sealed, from static files or the database, instead of
+            // reading both header tables and resealing.
+            let block_headers = self.sealed_headers_range(range.clone())?;
+            if block_headers.is_empty() {
+                return Ok(Vec::new())
+            }
+            block_headers
+        };
 
-        let block_header_hashes =
-            self.get_or_take::<tables::CanonicalHeaders, TAKE>(range.clone())?;
         let block_ommers = self.get_or_take::<tables::BlockOmmers, TAKE>(range.clone())?;
         let block_withdrawals =
             self.get_or_take::<tables::BlockWithdrawals, TAKE>(range.clone())?;
 
-        let block_tx = self.get_take_block_transaction_range::<TAKE>(range.clone())?;
-
-        if TAKE {
-            // rm HeaderTerminalDifficulties
-            self.get_or_take::<tables::HeaderTerminalDifficulties, TAKE>(range)?;
-            // rm HeaderNumbers
-            let mut header_number_cursor = self.tx.cursor_write::<tables::HeaderNumbers>()?;
-            for (_, hash) in block_header_hashes.iter() {
-                if header_number_cursor.seek_exact(*hash)?.is_some() {
-                    header_number_cursor.delete_current()?;
-                }
-            }
-        }
+        let block_tx = self.get_take_block_transaction_range::<TAKE>(range)?;
 
         // merge all into block
+        // There's exactly one block per header. Bodies are already sized by the `tx_count` of
+        // their block, so empty blocks don't allocate.
+        let mut blocks = Vec::with_capacity(block_headers.len());
         let block_header_iter = block_headers.into_iter();
-        let block_header_hashes_iter = block_header_hashes.into_iter();
         let block_tx_iter = block_tx.into_iter();
 
         // Ommers can be empty for some blocks
@@ -748,12 +1983,7 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         let mut block_ommers = block_ommers_iter.next();
         let mut block_withdrawals = block_withdrawals_iter.next();
 
-        let mut blocks = Vec::new();
-        for ((main_block_number, header), (_, header_hash), (_, tx)) in
-            izip!(block_header_iter.into_iter(), block_header_hashes_iter, block_tx_iter)
-        {
-            let header = header.seal(header_hash);
-
+        for (header, (main_block_number, tx)) in block_header_iter.zip(block_tx_iter) {
             let (body, senders) = tx.into_iter().map(|tx| tx.to_components()).unzip();
 
             // Ommers can be missing
@@ -843,6 +2073,42 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         Ok(())
     }
 
+    /// Unwind a table forward by a [Walker][reth_db::abstraction::cursor::Walker] on another table,
+    /// stopping once the limit is reached.
+    ///
+    /// Returns the last processed key of `T1` and whether the walk was finished. Deleting rows of
+    /// `T2` is idempotent, so an interrupted unwind can be resumed from the returned key.
+    pub fn unwind_table_by_walker_limited<T1, T2>(
+        &self,
+        start_at: T1::Key,
+        limiter: &mut PruneLimiter,
+    ) -> Result<(Option<T1::Key>, bool), DatabaseError>
+    where
+        T1: Table,
+        T2: Table<Key = T1::Value>,
+    {
+        let mut cursor = self.tx.cursor_write::<T1>()?;
+        let mut walker = cursor.walk(Some(start_at))?;
+        let mut last_key = None;
+        while let Some((key, value)) = walker.next().transpose()? {
+            if limiter.is_limit_reached() {
+                debug!(
+                    target: "providers::db",
+                    ?limiter,
+                    table = %T2::NAME,
+                    "Unwind limit reached"
+                );
+                return Ok((last_key, false))
+            }
+
+            if self.tx.delete::<T2>(value, None)? {
+                limiter.increment_deleted_entries_count();
+            }
+            last_key = Some(key);
+        }
+        Ok((last_key, true))
+    }
+
     /// Prune the table for the specified pre-sorted key iterator.
     ///
     /// Returns number of rows pruned.
@@ -930,6 +2196,121 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         Ok((deleted_entries, done))
     }
 
+    /// Prune the table for the specified key range, like
+    /// [`DatabaseProvider::prune_table_with_range`].
+    ///
+    /// Additionally returns the key of the last processed row, pruned or skipped, so that an
+    /// interrupted prune can be resumed right after it by passing `(Bound::Excluded(key), end)` as
+    /// the range. If the returned flag is `true`, the whole range was exhausted.
+    pub fn prune_table_with_range_resumable<T: Table>(
+        &self,
+        keys: impl RangeBounds<T::Key>,
+        limiter: &mut PruneLimiter,
+        mut skip_filter: impl FnMut(&TableRow<T>) -> bool,
+        delete_callback: impl FnMut(TableRow<T>),
+    ) -> Result<(usize, bool, Option<T::Key>), DatabaseError>
+    where
+        T::Key: Debug,
+    {
+        // Cursors can only walk from an included start key, so an excluded start bound is
+        // converted into the first key following it.
+        let start = match keys.start_bound().cloned() {
+            Bound::Excluded(start) => {
+                let mut cursor = self.tx.cursor_read::<T>()?;
+                let next = match cursor.seek(start.clone())? {
+                    Some((key, _)) if key == start => cursor.next()?,
+                    next => next,
+                };
+                match next {
+                    Some((key, _)) => Bound::Included(key),
+                    None => return Ok((0, true, None)),
+                }
+            }
+            bound => bound,
+        };
+
+        let mut last_key = None;
+        let (deleted_entries, done) = self.prune_table_with_range::<T>(
+            (start, keys.end_bound().cloned()),
+            limiter,
+            |row| {
+                last_key = Some(row.0.clone());
+                skip_filter(row)
+            },
+            delete_callback,
+        )?;
+
+        Ok((deleted_entries, done, last_key))
+    }
+
+    /// Prune the table for the specified key range, like
+    /// [`DatabaseProvider::prune_table_with_range`], but seeking past runs of kept rows instead of
+    /// stepping over them one by one.
+    ///
+    /// For each row in the range, `next_deletable_key` returns `None` if the row should be
+    /// deleted, or the key of the next row that may be deletable if it should be kept. In the
+    /// latter case, the cursor seeks to that key, skipping all rows in between. A returned key
+    /// that isn't greater than the key of the row is ignored, and the cursor steps to the next
+    /// row instead.
+    ///
+    /// Unlike [`DatabaseProvider::prune_table_with_range`], only deleted rows are counted.
+    ///
+    /// Returns number of rows pruned and whether the whole range was processed.
+    pub fn prune_table_with_range_seeking<T: Table>(
+        &self,
+        keys: impl RangeBounds<T::Key>,
+        limiter: &mut PruneLimiter,
+        mut next_deletable_key: impl FnMut(&TableRow<T>) -> Option<T::Key>,
+        mut delete_callback: impl FnMut(TableRow<T>),
+    ) -> Result<(usize, bool), DatabaseError> {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        let mut next = match keys.start_bound().cloned() {
+            Bound::Included(key) | Bound::Excluded(key) => cursor.seek(key)?,
+            Bound::Unbounded => cursor.first()?,
+        };
+        if matches!(
+            (keys.start_bound(), &next),
+            (Bound::Excluded(start), Some((key, _))) if key == start
+        ) {
+            next = cursor.next()?;
+        }
+
+        let mut deleted_entries = 0;
+
+        let done = loop {
+            let Some(row) = next else { break true };
+            if !keys.contains(&row.0) {
+                break true
+            }
+
+            if limiter.is_limit_reached() {
+                debug!(
+                    target: "providers::db",
+                    ?limiter,
+                    deleted_entries_limit = %limiter.is_deleted_entries_limit_reached(),
+                    time_limit = %limiter.is_time_limit_reached(),
+                    table = %T::NAME,
+                    "Pruning limit reached"
+                );
+                break false
+            }
+
+            next = match next_deletable_key(&row) {
+                Some(key) if key > row.0 => cursor.seek(key)?,
+                Some(_) => cursor.next()?,
+                None => {
+                    cursor.delete_current()?;
+                    limiter.increment_deleted_entries_count();
+                    deleted_entries += 1;
+                    delete_callback(row);
+                    cursor.next()?
+                }
+            };
+        };
+
+        Ok((deleted_entries, done))
+    }
+
     /// Steps once with the given walker and prunes the entry in the table.
     ///
     /// Returns `true` if the walker is finished, `false` if it may have more data to prune.
@@ -954,7 +2335,592 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
             delete_callback(row);
         }
 
-        Ok(false)
+        Ok(false)
+    }
+
+    /// Prune receipts of all transactions in the given block range and update the
+    /// [PruneSegment::Receipts] checkpoint.
+    ///
+    /// The block range is translated to a transaction range using [tables::BlockBodyIndices]. If
+    /// the limit is reached before all receipts are pruned, the checkpoint is set to the block
+    /// preceding the last pruned receipt, so that its remaining receipts are pruned on the next
+    /// run.
+    ///
+    /// Returns number of rows pruned and whether all receipts in the range were pruned.
+    pub fn prune_receipts_by_block_range(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, bool)> {
+        self.prune_tx_table_by_block_range::<tables::Receipts>(
+            block_range,
+            PruneSegment::Receipts,
+            prune_mode,
+            limiter,
+        )
+    }
+
+    /// Prune entries of a table keyed by [TxNumber] for all transactions in the given block range
+    /// and update the checkpoint of the given segment.
+    ///
+    /// See [`DatabaseProvider::prune_receipts_by_block_range`] for how the checkpoint is updated.
+    fn prune_tx_table_by_block_range<T: Table<Key = TxNumber>>(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        segment: PruneSegment,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, bool)> {
+        let (tx_range, done_checkpoint) = self.tx_range_with_done_checkpoint(block_range)?;
+
+        let mut last_pruned_transaction = None;
+        let (pruned, done) = self.prune_table_with_range::<T>(
+            tx_range,
+            limiter,
+            |_| false,
+            |row| last_pruned_transaction = Some(row.0),
+        )?;
+
+        self.save_tx_prune_checkpoint(
+            segment,
+            prune_mode,
+            done.then_some(done_checkpoint),
+            last_pruned_transaction,
+        )?;
+
+        Ok((pruned, done))
+    }
+
+    /// Prune [tables::TransactionHashNumbers] entries of all transactions in the given block range
+    /// and update the [PruneSegment::TransactionLookup] checkpoint.
+    ///
+    /// Entries are keyed by hash, so the transactions in the range are read and hashed first. The
+    /// checkpoint is updated the same way as in
+    /// [`DatabaseProvider::prune_receipts_by_block_range`].
+    ///
+    /// Returns number of rows pruned and whether all entries in the range were pruned.
+    pub fn prune_transaction_lookup_by_block_range(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, bool)> {
+        let (tx_range, done_checkpoint) = self.tx_range_with_done_checkpoint(block_range)?;
+
+        let (pruned, done, last_pruned_transaction) =
+            self.prune_transaction_lookup_entries(tx_range, limiter)?;
+
+        self.save_tx_prune_checkpoint(
+            PruneSegment::TransactionLookup,
+            prune_mode,
+            done.then_some(done_checkpoint),
+            last_pruned_transaction,
+        )?;
+
+        Ok((pruned, done))
+    }
+
+    /// Prune [tables::TransactionHashNumbers] entries of all transactions in the given
+    /// transaction range and update the [PruneSegment::TransactionLookup] checkpoint.
+    ///
+    /// Transactions in the range are read and hashed in parallel before pruning. The checkpoint is
+    /// updated the same way as in [`DatabaseProvider::prune_receipts_by_block_range`].
+    ///
+    /// Returns number of rows pruned and whether all entries in the range were pruned.
+    pub fn prune_transaction_lookup_for_tx_range(
+        &self,
+        tx_range: RangeInclusive<TxNumber>,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, bool)> {
+        let last_tx_num = *tx_range.end();
+        let (pruned, done, last_pruned_transaction) =
+            self.prune_transaction_lookup_entries(to_range(tx_range), limiter)?;
+
+        let done_checkpoint = if done {
+            let last_block = self
+                .transaction_block(last_tx_num)?
+                .ok_or(ProviderError::BlockNumberForTransactionIndexNotFound)?;
+            Some((last_block, Some(last_tx_num)))
+        } else {
+            None
+        };
+        self.save_tx_prune_checkpoint(
+            PruneSegment::TransactionLookup,
+            prune_mode,
+            done_checkpoint,
+            last_pruned_transaction,
+        )?;
+
+        Ok((pruned, done))
+    }
+
+    /// Read the transactions in the given range, compute their hashes in parallel and prune the
+    /// corresponding [tables::TransactionHashNumbers] entries.
+    ///
+    /// The range is capped to the number of entries the limiter still allows to delete before the
+    /// transactions are read, so that no more transactions than can be pruned are hashed.
+    ///
+    /// Returns number of rows pruned, whether all entries were pruned and the highest pruned
+    /// transaction number.
+    fn prune_transaction_lookup_entries(
+        &self,
+        tx_range: Range<TxNumber>,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, bool, Option<TxNumber>)> {
+        let capped_tx_range = match limiter.deleted_entries_limit_left() {
+            Some(left) => {
+                tx_range.start..tx_range.end.min(tx_range.start.saturating_add(left as u64))
+            }
+            None => tx_range.clone(),
+        };
+
+        let hashes = self
+            .transactions_by_tx_range(capped_tx_range.clone())?
+            .into_par_iter()
+            .map(|transaction| transaction.hash())
+            .collect::<Vec<_>>();
+
+        // Number of transactions retrieved from the database should match the tx range count
+        if hashes.len() as u64 != capped_tx_range.end.saturating_sub(capped_tx_range.start) {
+            return Err(ProviderError::TransactionNotFound(
+                (capped_tx_range.start + hashes.len() as u64).into(),
+            ))
+        }
+
+        let mut last_pruned_transaction = None;
+        let (pruned, done) = self.prune_table_with_iterator::<tables::TransactionHashNumbers>(
+            hashes,
+            limiter,
+            |row| {
+                last_pruned_transaction = Some(last_pruned_transaction.unwrap_or(row.1).max(row.1))
+            },
+        )?;
+
+        Ok((pruned, done && capped_tx_range.end == tx_range.end, last_pruned_transaction))
+    }
+
+    /// Translate the block range into its transaction range using
+    /// [`DatabaseProvider::tx_range_for_block_range`], along with the last block and transaction
+    /// number to checkpoint once the whole transaction range is pruned.
+    fn tx_range_with_done_checkpoint(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(Range<TxNumber>, (BlockNumber, Option<TxNumber>))> {
+        let last_block = *block_range.end();
+        let tx_range = match self.tx_range_for_block_range(block_range)? {
+            Some(tx_range) => tx_range,
+            // All blocks in the range are empty, so the checkpoint stays at the last transaction
+            // preceding the range.
+            None => {
+                let next_tx_num = self
+                    .block_body_indices(last_block)?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(last_block))?
+                    .next_tx_num();
+                next_tx_num..next_tx_num
+            }
+        };
+        let last_tx_num = tx_range.end.checked_sub(1);
+
+        Ok((tx_range, (last_block, last_tx_num)))
+    }
+
+    /// Save the [PruneCheckpoint] of a segment keyed by [TxNumber] after a prune run.
+    ///
+    /// If the run is done, `done_checkpoint` holds the last block and transaction number of the
+    /// pruned range. Otherwise, the checkpoint is set to the block preceding the last pruned
+    /// transaction, so that its remaining entries are pruned on the next run. If nothing was
+    /// pruned, the checkpoint stays as it is.
+    fn save_tx_prune_checkpoint(
+        &self,
+        segment: PruneSegment,
+        prune_mode: PruneMode,
+        done_checkpoint: Option<(BlockNumber, Option<TxNumber>)>,
+        last_pruned_transaction: Option<TxNumber>,
+    ) -> ProviderResult<()> {
+        let (block_number, tx_number) = if let Some((last_block, last_tx_num)) = done_checkpoint {
+            (Some(last_block), last_tx_num)
+        } else if let Some(tx_number) = last_pruned_transaction {
+            let block_number = self
+                .transaction_block(tx_number)?
+                .ok_or(ProviderError::BlockNumberForTransactionIndexNotFound)?
+                .checked_sub(1);
+            (block_number, Some(tx_number))
+        } else {
+            // Nothing was pruned, the checkpoint stays as it is.
+            return Ok(())
+        };
+
+        self.save_prune_checkpoint(segment, PruneCheckpoint { block_number, tx_number, prune_mode })
+    }
+
+    /// Prune the tables of the given segment up to and including `to_block` and update the
+    /// [PruneCheckpoint] of the segment.
+    ///
+    /// Pruning starts from the block following the current checkpoint of the segment, or from
+    /// genesis if there's none. Segments are pruned as follows:
+    /// - [PruneSegment::SenderRecovery]: [tables::TransactionSenders]
+    /// - [PruneSegment::TransactionLookup]: [tables::TransactionHashNumbers]
+    /// - [PruneSegment::Receipts]: [tables::Receipts]
+    /// - [PruneSegment::Transactions]: [tables::Transactions]
+    /// - [PruneSegment::AccountHistory]: [tables::AccountChangeSets] and [tables::AccountsHistory]
+    /// - [PruneSegment::StorageHistory]: [tables::StorageChangeSets] and [tables::StoragesHistory]
+    ///
+    /// [PruneSegment::ContractLogs] and [PruneSegment::Headers] are not supported.
+    ///
+    /// Returns number of rows pruned and whether the segment was pruned up to `to_block`.
+    pub fn prune_segment(
+        &self,
+        segment: PruneSegment,
+        to_block: BlockNumber,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, bool)> {
+        let from_block = self
+            .get_prune_checkpoint(segment)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1);
+        if from_block > to_block {
+            return Ok((0, true))
+        }
+        let block_range = from_block..=to_block;
+
+        match segment {
+            PruneSegment::SenderRecovery => self
+                .prune_tx_table_by_block_range::<tables::TransactionSenders>(
+                    block_range,
+                    segment,
+                    prune_mode,
+                    limiter,
+                ),
+            PruneSegment::TransactionLookup => {
+                self.prune_transaction_lookup_by_block_range(block_range, prune_mode, limiter)
+            }
+            PruneSegment::Receipts => {
+                self.prune_receipts_by_block_range(block_range, prune_mode, limiter)
+            }
+            PruneSegment::Transactions => self
+                .prune_tx_table_by_block_range::<tables::Transactions>(
+                    block_range,
+                    segment,
+                    prune_mode,
+                    limiter,
+                ),
+            PruneSegment::AccountHistory => {
+                self.prune_account_history_by_block_range(block_range, prune_mode, limiter)
+            }
+            PruneSegment::StorageHistory => {
+                self.prune_storage_history_by_block_range(block_range, prune_mode, limiter)
+            }
+            PruneSegment::ContractLogs | PruneSegment::Headers => {
+                Err(ProviderError::UnsupportedPruneSegment(segment))
+            }
+        }
+    }
+
+    /// Prune [tables::AccountChangeSets] in the given block range and [tables::AccountsHistory]
+    /// indices up to the last pruned changeset, and update the [PruneSegment::AccountHistory]
+    /// checkpoint.
+    ///
+    /// Returns number of rows pruned and whether all changesets in the range were pruned.
+    pub fn prune_account_history_by_block_range(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, bool)> {
+        let (pruned_changesets, pruned_indices, done) =
+            self.prune_account_history(block_range, prune_mode, limiter)?;
+        Ok((pruned_changesets + pruned_indices, done))
+    }
+
+    /// Prune [tables::StorageChangeSets] in the given block range and [tables::StoragesHistory]
+    /// indices up to the last pruned changeset, and update the [PruneSegment::StorageHistory]
+    /// checkpoint.
+    ///
+    /// Returns number of rows pruned and whether all changesets in the range were pruned.
+    pub fn prune_storage_history_by_block_range(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, bool)> {
+        let (pruned_changesets, pruned_indices, done) =
+            self.prune_storage_history(block_range, prune_mode, limiter)?;
+        Ok((pruned_changesets + pruned_indices, done))
+    }
+
+    /// Prune [tables::AccountChangeSets] in the given block range and update the
+    /// [PruneSegment::AccountHistory] checkpoint.
+    ///
+    /// The checkpoint is shared with [`DatabaseProvider::prune_account_history_by_block_range`],
+    /// so the [tables::AccountsHistory] indices are pruned up to the last pruned changeset as well,
+    /// but they don't count towards the returned number of rows.
+    ///
+    /// Returns number of changesets pruned and whether all changesets in the range were pruned.
+    pub fn prune_account_changesets(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, bool)> {
+        let (pruned_changesets, _, done) =
+            self.prune_account_history(block_range, prune_mode, limiter)?;
+        Ok((pruned_changesets, done))
+    }
+
+    /// Prune [tables::StorageChangeSets] in the given block range and update the
+    /// [PruneSegment::StorageHistory] checkpoint.
+    ///
+    /// The checkpoint is shared with [`DatabaseProvider::prune_storage_history_by_block_range`],
+    /// so the [tables::StoragesHistory] indices are pruned up to the last pruned changeset as well,
+    /// but they don't count towards the returned number of rows.
+    ///
+    /// Returns number of changesets pruned and whether all changesets in the range were pruned.
+    pub fn prune_storage_changesets(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, bool)> {
+        let (pruned_changesets, _, done) =
+            self.prune_storage_history(block_range, prune_mode, limiter)?;
+        Ok((pruned_changesets, done))
+    }
+
+    /// Prune [tables::AccountChangeSets] and [tables::AccountsHistory] indices, see
+    /// [`DatabaseProvider::prune_history_by_block_range`].
+    fn prune_account_history(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, usize, bool)> {
+        let range_end = *block_range.end();
+        self.prune_history_by_block_range::<tables::AccountChangeSets, tables::AccountsHistory, _>(
+            block_range,
+            range_end,
+            |block_number| block_number,
+            |a, b| a.key == b.key,
+            |key| ShardedKey::last(key.key),
+            PruneSegment::AccountHistory,
+            prune_mode,
+            limiter,
+        )
+    }
+
+    /// Prune [tables::StorageChangeSets] and [tables::StoragesHistory] indices, see
+    /// [`DatabaseProvider::prune_history_by_block_range`].
+    fn prune_storage_history(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, usize, bool)> {
+        let range_end = *block_range.end();
+        self.prune_history_by_block_range::<tables::StorageChangeSets, tables::StoragesHistory, _>(
+            BlockNumberAddress::range(block_range),
+            range_end,
+            |key| key.block_number(),
+            |a, b| a.address == b.address && a.sharded_key.key == b.sharded_key.key,
+            |key| StorageShardedKey::last(key.address, key.sharded_key.key),
+            PruneSegment::StorageHistory,
+            prune_mode,
+            limiter,
+        )
+    }
+
+    /// Prune the changesets in the given range and the history indices up to the last pruned
+    /// changeset block, then update the checkpoint of the given segment.
+    ///
+    /// If the limit is reached before all changesets are pruned, the checkpoint is set to the block
+    /// preceding the last pruned changeset, so that its remaining changesets are pruned on the
+    /// next run.
+    ///
+    /// Returns number of changesets pruned, number of history indices pruned and whether all
+    /// changesets in the range were pruned.
+    #[allow(clippy::too_many_arguments)]
+    fn prune_history_by_block_range<C, H, SK>(
+        &self,
+        changeset_range: impl RangeBounds<C::Key> + Clone + Debug,
+        range_end: BlockNumber,
+        changeset_block: impl Fn(C::Key) -> BlockNumber,
+        key_matches: impl Fn(&H::Key, &H::Key) -> bool,
+        last_key: impl Fn(&H::Key) -> H::Key,
+        segment: PruneSegment,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, usize, bool)>
+    where
+        C: Table,
+        H: Table<Value = BlockNumberList>,
+        H::Key: AsRef<ShardedKey<SK>>,
+    {
+        if limiter.is_limit_reached() {
+            return Ok((0, 0, false))
+        }
+
+        let (pruned_changesets, done, last_changeset_pruned_block) = self
+            .prune_changesets_by_block_range::<C>(
+                changeset_range,
+                range_end,
+                changeset_block,
+                segment,
+                prune_mode,
+                limiter,
+            )?;
+
+        let (_, pruned_indices) = self.prune_history_indices::<H, SK>(
+            last_changeset_pruned_block,
+            key_matches,
+            last_key,
+        )?;
+
+        Ok((pruned_changesets, pruned_indices, done))
+    }
+
+    /// Prune the changesets in the given range and update the checkpoint of the given segment.
+    ///
+    /// If the limit is reached before all changesets are pruned, the checkpoint is set to the block
+    /// preceding the last pruned changeset, so that its remaining changesets are pruned on the
+    /// next run.
+    ///
+    /// Returns number of rows pruned, whether all changesets in the range were pruned and the
+    /// block number the checkpoint was set to.
+    fn prune_changesets_by_block_range<C: Table>(
+        &self,
+        changeset_range: impl RangeBounds<C::Key> + Clone + Debug,
+        range_end: BlockNumber,
+        changeset_block: impl Fn(C::Key) -> BlockNumber,
+        segment: PruneSegment,
+        prune_mode: PruneMode,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<(usize, bool, BlockNumber)> {
+        let mut last_changeset_pruned_block = None;
+        let (pruned_changesets, done) = self.prune_table_with_range::<C>(
+            changeset_range,
+            limiter,
+            |_| false,
+            |row| last_changeset_pruned_block = Some(changeset_block(row.0)),
+        )?;
+
+        let last_changeset_pruned_block = last_changeset_pruned_block
+            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .unwrap_or(range_end);
+
+        self.save_prune_checkpoint(
+            segment,
+            PruneCheckpoint {
+                block_number: Some(last_changeset_pruned_block),
+                tx_number: None,
+                prune_mode,
+            },
+        )?;
+
+        Ok((pruned_changesets, done, last_changeset_pruned_block))
+    }
+
+    /// Prune history indices up to the provided block, inclusive.
+    ///
+    /// Returns total number of processed (walked) and deleted entities.
+    pub fn prune_history_indices<T, SK>(
+        &self,
+        to_block: BlockNumber,
+        key_matches: impl Fn(&T::Key, &T::Key) -> bool,
+        last_key: impl Fn(&T::Key) -> T::Key,
+    ) -> Result<(usize, usize), DatabaseError>
+    where
+        T: Table<Value = BlockNumberList>,
+        T::Key: AsRef<ShardedKey<SK>>,
+    {
+        let mut processed = 0;
+        let mut deleted = 0;
+        let mut cursor = self.tx.cursor_write::<T>()?;
+
+        // Prune history table:
+        // 1. If the shard has `highest_block_number` less than or equal to the target block number
+        // for pruning, delete the shard completely.
+        // 2. If the shard has `highest_block_number` greater than the target block number for
+        // pruning, filter block numbers inside the shard which are less than the target
+        // block number for pruning.
+        while let Some(result) = cursor.next()? {
+            let (key, blocks): (T::Key, BlockNumberList) = result;
+
+            // If shard consists only of block numbers less than the target one, delete shard
+            // completely.
+            if key.as_ref().highest_block_number <= to_block {
+                cursor.delete_current()?;
+                deleted += 1;
+                if key.as_ref().highest_block_number == to_block {
+                    // Shard contains only block numbers up to the target one, so we can skip to
+                    // the last shard for this key. It is guaranteed that further shards for this
+                    // sharded key will not contain the target block number, as it's in this shard.
+                    cursor.seek_exact(last_key(&key))?;
+                }
+            }
+            // Shard contains block numbers that are higher than the target one, so we need to
+            // filter it. It is guaranteed that further shards for this sharded key will not
+            // contain the target block number, as it's in this shard.
+            else {
+                let higher_blocks =
+                    blocks.iter().skip_while(|block| *block <= to_block).collect::<Vec<_>>();
+
+                // If there were blocks less than or equal to the target one
+                // (so the shard has changed), update the shard.
+                if blocks.len() as usize != higher_blocks.len() {
+                    // If there will be no more blocks in the shard after pruning blocks below
+                    // target block, we need to remove it, as empty shards are
+                    // not allowed.
+                    if higher_blocks.is_empty() {
+                        if key.as_ref().highest_block_number == u64::MAX {
+                            let prev_row = cursor.prev()?;
+                            match prev_row {
+                                // If current shard is the last shard for the sharded key that
+                                // has previous shards, replace it with the previous shard.
+                                Some((prev_key, prev_value)) if key_matches(&prev_key, &key) => {
+                                    cursor.delete_current()?;
+                                    deleted += 1;
+                                    // Upsert will replace the last shard for this sharded key with
+                                    // the previous value.
+                                    cursor.upsert(key.clone(), prev_value)?;
+                                }
+                                // If there's no previous shard for this sharded key,
+                                // just delete last shard completely.
+                                _ => {
+                                    // If we successfully moved the cursor to a previous row,
+                                    // jump to the original last shard.
+                                    if prev_row.is_some() {
+                                        cursor.next()?;
+                                    }
+                                    // Delete shard.
+                                    cursor.delete_current()?;
+                                    deleted += 1;
+                                }
+                            }
+                        }
+                        // If current shard is not the last shard for this sharded key,
+                        // just delete it.
+                        else {
+                            cursor.delete_current()?;
+                            deleted += 1;
+                        }
+                    } else {
+                        cursor
+                            .upsert(key.clone(), BlockNumberList::new_pre_sorted(higher_blocks))?;
+                    }
+                }
+
+                // Jump to the last shard for this key, if current key isn't already the last shard.
+                if key.as_ref().highest_block_number != u64::MAX {
+                    cursor.seek_exact(last_key(&key))?;
+                }
+            }
+
+            processed += 1;
+        }
+
+        Ok((processed, deleted))
     }
 
     /// Load shard and remove it. If list is empty, last shard was full or
@@ -991,30 +2957,256 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         T: Table<Value = BlockNumberList>,
     {
         for (partial_key, indices) in index_updates {
-            let last_shard = self.take_shard::<T>(sharded_key_factory(partial_key, u64::MAX))?;
-            // chunk indices and insert them in shards of N size.
-            let indices = last_shard.iter().chain(indices.iter());
-            let chunks = indices
-                .chunks(sharded_key::NUM_OF_INDICES_IN_SHARD)
-                .into_iter()
-                .map(|chunks| chunks.copied().collect())
-                .collect::<Vec<Vec<_>>>();
+            self.append_history_index_for_key::<P, T>(
+                partial_key,
+                indices,
+                &mut sharded_key_factory,
+            )?;
+        }
+        Ok(())
+    }
 
-            let mut chunks = chunks.into_iter().peekable();
-            while let Some(list) = chunks.next() {
-                let highest_block_number = if chunks.peek().is_some() {
-                    *list.last().expect("`chunks` does not return empty list")
-                } else {
-                    // Insert last list with u64::MAX
-                    u64::MAX
-                };
-                self.tx.put::<T>(
-                    sharded_key_factory(partial_key, highest_block_number),
-                    BlockNumberList::new_pre_sorted(list),
+    /// Insert history index to the database, until the limit of the [PruneLimiter] is reached.
+    ///
+    /// Same as [`Self::append_history_index`], but partial keys are processed one at a time and
+    /// the limiter is checked before each of them. Every inserted shard is counted as a deleted
+    /// entry towards the limit. A partial key is always processed as a whole, so resuming with
+    /// the returned remainder produces the same shards as a single uninterrupted run.
+    ///
+    /// Returns the index updates that haven't been processed yet, which are empty if the run
+    /// has finished.
+    pub fn append_history_index_limited<P, T>(
+        &self,
+        index_updates: BTreeMap<P, Vec<u64>>,
+        mut sharded_key_factory: impl FnMut(P, BlockNumber) -> T::Key,
+        limiter: &mut PruneLimiter,
+    ) -> ProviderResult<BTreeMap<P, Vec<u64>>>
+    where
+        P: Copy + Ord,
+        T: Table<Value = BlockNumberList>,
+    {
+        let mut index_updates = index_updates.into_iter();
+        while !limiter.is_limit_reached() {
+            let Some((partial_key, indices)) = index_updates.next() else { break };
+            let shards = self.append_history_index_for_key::<P, T>(
+                partial_key,
+                indices,
+                &mut sharded_key_factory,
+            )?;
+            limiter.increment_deleted_entries_count_by(shards);
+        }
+        Ok(index_updates.collect())
+    }
+
+    /// Insert history index of a single partial key to the database.
+    ///
+    /// Returns the number of inserted shards.
+    fn append_history_index_for_key<P, T>(
+        &self,
+        partial_key: P,
+        indices: Vec<u64>,
+        sharded_key_factory: &mut impl FnMut(P, BlockNumber) -> T::Key,
+    ) -> ProviderResult<usize>
+    where
+        P: Copy,
+        T: Table<Value = BlockNumberList>,
+    {
+        let last_shard = self.take_shard::<T>(sharded_key_factory(partial_key, u64::MAX))?;
+        // chunk indices and insert them in shards of N size.
+        let indices = last_shard.iter().chain(indices.iter());
+        let chunks = indices
+            .chunks(sharded_key::NUM_OF_INDICES_IN_SHARD)
+            .into_iter()
+            .map(|chunks| chunks.copied().collect())
+            .collect::<Vec<Vec<_>>>();
+        let shards = chunks.len();
+
+        let mut chunks = chunks.into_iter().peekable();
+        while let Some(list) = chunks.next() {
+            let highest_block_number = if chunks.peek().is_some() {
+                *list.last().expect("`chunks` does not return empty list")
+            } else {
+                // Insert last list with u64::MAX
+                u64::MAX
+            };
+            self.tx.put::<T>(
+                sharded_key_factory(partial_key, highest_block_number),
+                BlockNumberList::new_pre_sorted(list),
+            )?;
+        }
+        Ok(shards)
+    }
+
+    /// Inserts the block the same way as [`BlockWriter::insert_block`], but first checks that
+    /// the block number isn't already occupied by a different block in
+    /// [tables::CanonicalHeaders].
+    ///
+    /// Inserting the same block again is allowed. Returns [ProviderError::BlockNumberOccupied] on
+    /// conflict, without writing anything.
+    pub fn insert_block_checked(
+        &self,
+        block: SealedBlockWithSenders,
+        prune_modes: Option<&PruneModes>,
+    ) -> ProviderResult<StoredBlockBodyIndices> {
+        let block_number = block.number;
+        if let Some(existing) = self.tx.get::<tables::CanonicalHeaders>(block_number)? {
+            if existing != block.hash() {
+                return Err(ProviderError::BlockNumberOccupied {
+                    block_number,
+                    existing,
+                    hash: block.hash(),
+                })
+            }
+        }
+
+        self.insert_block(block, prune_modes)
+    }
+
+    /// Inserts the block the same way as [`BlockWriter::insert_block`], additionally returning
+    /// the hashes of the block's transactions that were computed during insertion.
+    pub fn insert_block_with_hashes(
+        &self,
+        block: SealedBlockWithSenders,
+        prune_modes: Option<&PruneModes>,
+    ) -> ProviderResult<(StoredBlockBodyIndices, Vec<TxHash>)> {
+        let block_number = block.number;
+
+        let mut durations_recorder = metrics::DurationsRecorder::new(self.insert_block_metrics);
+
+        self.tx.put::<tables::CanonicalHeaders>(block_number, block.hash())?;
+        durations_recorder.record_relative(metrics::Action::InsertCanonicalHeaders);
+
+        // Put header with canonical hashes.
+        self.tx.put::<tables::Headers>(block_number, block.header.as_ref().clone())?;
+        durations_recorder.record_relative(metrics::Action::InsertHeaders);
+
+        self.tx.put::<tables::HeaderNumbers>(block.hash(), block_number)?;
+        durations_recorder.record_relative(metrics::Action::InsertHeaderNumbers);
+
+        // total difficulty
+        let ttd = if block_number == 0 {
+            block.difficulty
+        } else {
+            let parent_block_number = block_number - 1;
+            let parent_ttd = self.header_td_by_number(parent_block_number)?.unwrap_or_default();
+            durations_recorder.record_relative(metrics::Action::GetParentTD);
+            parent_ttd + block.difficulty
+        };
+
+        self.tx.put::<tables::HeaderTerminalDifficulties>(block_number, ttd.into())?;
+        durations_recorder.record_relative(metrics::Action::InsertHeaderTerminalDifficulties);
+
+        // insert body ommers data
+        if !block.ommers.is_empty() {
+            self.tx.put::<tables::BlockOmmers>(
+                block_number,
+                StoredBlockOmmers { ommers: block.block.ommers },
+            )?;
+            durations_recorder.record_relative(metrics::Action::InsertBlockOmmers);
+        }
+
+        let mut next_tx_num = self
+            .tx
+            .cursor_read::<tables::TransactionBlocks>()?
+            .last()?
+            .map(|(n, _)| n + 1)
+            .unwrap_or_default();
+        durations_recorder.record_relative(metrics::Action::GetNextTxNum);
+        let first_tx_num = next_tx_num;
+
+        let tx_count = block.block.body.len() as u64;
+        let mut tx_hashes = Vec::with_capacity(tx_count as usize);
+
+        // Skip writing senders and transaction lookup entries if the block falls into the
+        // respective prune window. The tip is the highest known block, or the block being
+        // inserted if it's the new tip.
+        let tip = self.last_block_number()?.max(block_number);
+        let prune_senders = prune_modes
+            .and_then(|modes| modes.sender_recovery)
+            .is_some_and(|prune_mode| prune_mode.should_prune(block_number, tip));
+        let prune_transaction_lookup = prune_modes
+            .and_then(|modes| modes.transaction_lookup)
+            .is_some_and(|prune_mode| prune_mode.should_prune(block_number, tip));
+
+        // Ensures we have all the senders for the block's transactions.
+        let mut tx_senders_elapsed = Duration::default();
+        let mut transactions_elapsed = Duration::default();
+        let mut tx_hash_numbers_elapsed = Duration::default();
+
+        for (transaction, sender) in block.block.body.into_iter().zip(block.senders.iter()) {
+            let hash = transaction.hash();
+
+            if !prune_senders {
+                let start = durations_recorder.is_enabled().then(Instant::now);
+                self.tx.put::<tables::TransactionSenders>(next_tx_num, *sender)?;
+                if let Some(start) = start {
+                    tx_senders_elapsed += start.elapsed();
+                }
+            }
+
+            let start = durations_recorder.is_enabled().then(Instant::now);
+            self.tx.put::<tables::Transactions>(next_tx_num, transaction.into())?;
+            if let Some(start) = start {
+                let elapsed = start.elapsed();
+                if elapsed > Duration::from_secs(1) {
+                    warn!(
+                        target: "providers::db",
+                        ?block_number,
+                        tx_num = %next_tx_num,
+                        hash = %hash,
+                        ?elapsed,
+                        "Transaction insertion took too long"
+                    );
+                }
+                transactions_elapsed += elapsed;
+            }
+
+            if !prune_transaction_lookup {
+                let start = durations_recorder.is_enabled().then(Instant::now);
+                self.tx.put::<tables::TransactionHashNumbers>(hash, next_tx_num)?;
+                if let Some(start) = start {
+                    tx_hash_numbers_elapsed += start.elapsed();
+                }
+            }
+            tx_hashes.push(hash);
+            next_tx_num += 1;
+        }
+        durations_recorder
+            .record_duration(metrics::Action::InsertTransactionSenders, tx_senders_elapsed);
+        durations_recorder
+            .record_duration(metrics::Action::InsertTransactions, transactions_elapsed);
+        durations_recorder.record_duration(
+            metrics::Action::InsertTransactionHashNumbers,
+            tx_hash_numbers_elapsed,
+        );
+
+        if let Some(withdrawals) = block.block.withdrawals {
+            if !withdrawals.is_empty() {
+                self.tx.put::<tables::BlockWithdrawals>(
+                    block_number,
+                    StoredBlockWithdrawals { withdrawals },
                 )?;
+                durations_recorder.record_relative(metrics::Action::InsertBlockWithdrawals);
             }
         }
-        Ok(())
+
+        let block_indices = StoredBlockBodyIndices { first_tx_num, tx_count };
+        self.tx.put::<tables::BlockBodyIndices>(block_number, block_indices.clone())?;
+        durations_recorder.record_relative(metrics::Action::InsertBlockBodyIndices);
+
+        if !block_indices.is_empty() {
+            self.tx.put::<tables::TransactionBlocks>(block_indices.last_tx_num(), block_number)?;
+            durations_recorder.record_relative(metrics::Action::InsertTransactionBlocks);
+        }
+
+        debug!(
+            target: "providers::db",
+            ?block_number,
+            actions = ?durations_recorder.actions,
+            "Inserted block"
+        );
+
+        Ok((block_indices, tx_hashes))
     }
 }
 
@@ -1029,13 +3221,11 @@ impl<TX: DbTx> AccountExtReader for DatabaseProvider<TX> {
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeSet<Address>> {
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
-            })
-            .collect()
+        let mut accounts = BTreeSet::new();
+        self.for_each_account_change(range, |_, account_before| {
+            accounts.insert(account_before.address);
+        })?;
+        Ok(accounts)
     }
 
     fn basic_accounts(
@@ -1053,16 +3243,10 @@ impl<TX: DbTx> AccountExtReader for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
-
-        let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
-            BTreeMap::new(),
-            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
-                let (index, account) = entry?;
-                accounts.entry(account.address).or_default().push(index);
-                Ok(accounts)
-            },
-        )?;
+        let mut account_transitions: BTreeMap<Address, Vec<u64>> = BTreeMap::new();
+        self.for_each_account_change(range, |index, account| {
+            account_transitions.entry(account.address).or_default().push(index);
+        })?;
 
         Ok(account_transitions)
     }
@@ -1073,15 +3257,11 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
-            })
-            .collect()
+        let mut changeset = Vec::new();
+        self.for_each_account_change(block_number..=block_number, |_, account_before| {
+            changeset.push(account_before)
+        })?;
+        Ok(changeset)
     }
 }
 
@@ -1187,9 +3367,7 @@ impl<TX: DbTx> HeaderProvider for DatabaseProvider<TX> {
             |static_file| static_file.sealed_header(number),
             || {
                 if let Some(header) = self.header_by_number(number)? {
-                    let hash = self
-                        .block_hash(number)?
-                        .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                    let hash = self.canonical_hash_checked(number)?;
                     Ok(Some(header.seal(hash)))
                 } else {
                     Ok(None)
@@ -1198,6 +3376,11 @@ impl<TX: DbTx> HeaderProvider for DatabaseProvider<TX> {
         )
     }
 
+    fn latest_sealed_header(&self) -> ProviderResult<Option<SealedHeader>> {
+        // On an empty database this resolves to block 0, which has no header either.
+        self.sealed_header(self.last_block_number()?)
+    }
+
     fn sealed_headers_while(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -1211,9 +3394,7 @@ impl<TX: DbTx> HeaderProvider for DatabaseProvider<TX> {
                 let mut headers = vec![];
                 for entry in self.tx.cursor_read::<tables::Headers>()?.walk_range(range)? {
                     let (number, header) = entry?;
-                    let hash = self
-                        .block_hash(number)?
-                        .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                    let hash = self.canonical_hash_checked(number)?;
                     let sealed = header.seal(hash);
                     if !predicate(&sealed) {
                         break
@@ -1344,15 +3525,118 @@ impl<Tx: DbTx> DatabaseProvider<Tx> {
         }
         Ok(blocks)
     }
+
+    /// Returns the block with senders like [`BlockReader::block_with_senders`], but requires all
+    /// senders to be present in [tables::TransactionSenders] instead of recovering the missing
+    /// ones.
+    ///
+    /// Returns [ProviderError::MismatchOfTransactionAndSenderId] for the first transaction without
+    /// a stored sender, e.g. because senders were pruned.
+    pub fn block_with_stored_senders(
+        &self,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<BlockWithSenders>> {
+        let Some(block_number) = self.convert_hash_or_number(id)? else { return Ok(None) };
+        let Some(header) = self.header_by_number(block_number)? else { return Ok(None) };
+
+        self.block_with_senders_for_header(block_number, header, transaction_kind, false)
+    }
+
+    /// Assembles the [BlockWithSenders] for the given block number and its already fetched header.
+    ///
+    /// Senders missing from [tables::TransactionSenders], e.g. because they were pruned, are
+    /// recovered from their transactions if `recover_missing_senders` is set, otherwise an error is
+    /// returned.
+    ///
+    /// If the header is found, but the transactions either do not exist, or are not indexed, this
+    /// will return None.
+    fn block_with_senders_for_header(
+        &self,
+        block_number: BlockNumber,
+        header: Header,
+        transaction_kind: TransactionVariant,
+        recover_missing_senders: bool,
+    ) -> ProviderResult<Option<BlockWithSenders>> {
+        let ommers = self.ommers(block_number.into())?.unwrap_or_default();
+        let withdrawals = self.withdrawals_by_block(block_number.into(), header.timestamp)?;
+
+        // Get the block body
+        //
+        // If the body indices are not found, this means that the transactions either do not exist
+        // in the database yet, or they do exit but are not indexed. If they exist but are not
+        // indexed, we don't have enough information to return the block anyways, so we return
+        // `None`.
+        let Some(body) = self.block_body_indices(block_number)? else { return Ok(None) };
+
+        let tx_range = body.tx_num_range();
+
+        let transactions = self.transactions_with_numbers_with_cursor(
+            tx_range,
+            &mut self.tx.cursor_read::<tables::Transactions>()?,
+        )?;
+        let senders = self
+            .senders_of_transactions(
+                &transactions,
+                &mut self.tx.cursor_read::<tables::TransactionSenders>()?,
+                recover_missing_senders,
+            )?
+            .into_iter()
+            .zip(&transactions)
+            .map(|(sender, (tx_num, _))| {
+                sender.ok_or(if recover_missing_senders {
+                    ProviderError::TransactionSenderRecoveryError(*tx_num)
+                } else {
+                    ProviderError::MismatchOfTransactionAndSenderId { tx_id: *tx_num }
+                })
+            })
+            .collect::<ProviderResult<Vec<_>>>()?;
+
+        let body = transactions
+            .into_iter()
+            .map(|(_, tx)| match transaction_kind {
+                TransactionVariant::NoHash => TransactionSigned {
+                    // Caller explicitly asked for no hash, so we don't calculate it
+                    hash: B256::ZERO,
+                    signature: tx.signature,
+                    transaction: tx.transaction,
+                },
+                TransactionVariant::WithHash => tx.with_hash(),
+            })
+            .collect();
+
+        Block { header, body, ommers, withdrawals }
+            // Note: we're using unchecked here because we know the block contains valid txs wrt to
+            // its height and can ignore the s value check so pre EIP-2 txs are allowed
+            .try_with_senders_unchecked(senders)
+            .map(Some)
+            .map_err(|_| ProviderError::SenderRecoveryError)
+    }
 }
 
 impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
     fn find_block_by_hash(&self, hash: B256, source: BlockSource) -> ProviderResult<Option<Block>> {
+        Ok(self.find_sealed_block_by_hash(hash, source)?.map(SealedBlock::unseal))
+    }
+
+    /// Looks up the block in the database first, if the source allows it. The pending block is
+    /// only consulted if the block is not in the database.
+    fn find_sealed_block_by_hash(
+        &self,
+        hash: B256,
+        source: BlockSource,
+    ) -> ProviderResult<Option<SealedBlock>> {
         if source.is_database() {
-            self.block(hash.into())
-        } else {
-            Ok(None)
+            if let Some(block) = self.block(hash.into())? {
+                return Ok(Some(block.seal(hash)))
+            }
+        }
+
+        if source.is_pending() {
+            return Ok(self.pending_block()?.filter(|block| block.hash() == hash))
         }
+
+        Ok(None)
     }
 
     /// Returns the block with matching number from database.
@@ -1408,10 +3692,50 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
         Ok(None)
     }
 
+    fn ommers_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Header>)>> {
+        // Only blocks with ommers have an entry in the table, so it's walked separately from the
+        // block bodies.
+        let mut ommers = self
+            .tx
+            .cursor_read::<tables::BlockOmmers>()?
+            .walk_range(range.clone())?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        let mut body_cursor = self.tx.cursor_read::<tables::BlockBodyIndices>()?;
+        body_cursor
+            .walk_range(range)?
+            .map(|entry| {
+                let (number, _) = entry?;
+                // If the Paris (Merge) hardfork block is known and block is after it, return empty
+                // ommers.
+                let ommers = if self.chain_spec.final_paris_total_difficulty(number).is_some() {
+                    Vec::new()
+                } else {
+                    ommers.remove(&number).map(|o| o.ommers).unwrap_or_default()
+                };
+                Ok((number, ommers))
+            })
+            .collect()
+    }
+
     fn block_body_indices(&self, num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
         Ok(self.tx.get::<tables::BlockBodyIndices>(num)?)
     }
 
+    fn block_body_indices_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, StoredBlockBodyIndices)>> {
+        Ok(self
+            .tx
+            .cursor_read::<tables::BlockBodyIndices>()?
+            .walk_range(range)?
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
     /// Returns the block with senders with matching number or hash from database.
     ///
     /// **NOTE: The transactions have invalid hashes, since they would need to be calculated on the
@@ -1428,44 +3752,22 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
         let Some(block_number) = self.convert_hash_or_number(id)? else { return Ok(None) };
         let Some(header) = self.header_by_number(block_number)? else { return Ok(None) };
 
-        let ommers = self.ommers(block_number.into())?.unwrap_or_default();
-        let withdrawals = self.withdrawals_by_block(block_number.into(), header.timestamp)?;
-
-        // Get the block body
-        //
-        // If the body indices are not found, this means that the transactions either do not exist
-        // in the database yet, or they do exit but are not indexed. If they exist but are not
-        // indexed, we don't have enough information to return the block anyways, so we return
-        // `None`.
-        let Some(body) = self.block_body_indices(block_number)? else { return Ok(None) };
-
-        let tx_range = body.tx_num_range();
-
-        let (transactions, senders) = if tx_range.is_empty() {
-            (vec![], vec![])
-        } else {
-            (self.transactions_by_tx_range(tx_range.clone())?, self.senders_by_tx_range(tx_range)?)
-        };
+        self.block_with_senders_for_header(block_number, header, transaction_kind, true)
+    }
 
-        let body = transactions
-            .into_iter()
-            .map(|tx| match transaction_kind {
-                TransactionVariant::NoHash => TransactionSigned {
-                    // Caller explicitly asked for no hash, so we don't calculate it
-                    hash: B256::ZERO,
-                    signature: tx.signature,
-                    transaction: tx.transaction,
-                },
-                TransactionVariant::WithHash => tx.with_hash(),
-            })
-            .collect();
+    fn sealed_block_with_senders(
+        &self,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        let Some(block_number) = self.convert_hash_or_number(id)? else { return Ok(None) };
+        let Some(header) = self.sealed_header(block_number)? else { return Ok(None) };
 
-        Block { header, body, ommers, withdrawals }
-            // Note: we're using unchecked here because we know the block contains valid txs wrt to
-            // its height and can ignore the s value check so pre EIP-2 txs are allowed
-            .try_with_senders_unchecked(senders)
-            .map(Some)
-            .map_err(|_| ProviderError::SenderRecoveryError)
+        // Reuse the hash of the stored sealed header instead of recomputing it.
+        let (header, hash) = header.split();
+        Ok(self
+            .block_with_senders_for_header(block_number, header, transaction_kind, true)?
+            .map(|block| block.seal(hash)))
     }
 
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
@@ -1488,44 +3790,50 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<Vec<BlockWithSenders>> {
         let mut tx_cursor = self.tx.cursor_read::<tables::Transactions>()?;
-        let mut senders_cursor = self.tx.cursor_read::<tables::TransactionSenders>()?;
 
-        self.process_block_range(range, |tx_range, header, ommers, withdrawals| {
-            let (body, senders) = if tx_range.is_empty() {
-                (Vec::new(), Vec::new())
+        // Collect the transactions of all blocks first, so that the missing senders of the whole
+        // range are recovered in parallel instead of one block at a time.
+        let mut transactions = Vec::new();
+        let blocks = self.process_block_range(range, |tx_range, header, ommers, withdrawals| {
+            let block_transactions = if tx_range.is_empty() {
+                Vec::new()
             } else {
-                let body = self
-                    .transactions_by_tx_range_with_cursor(tx_range.clone(), &mut tx_cursor)?
-                    .into_iter()
-                    .map(Into::into)
-                    .collect::<Vec<TransactionSigned>>();
-                // fetch senders from the senders table
-                let known_senders =
-                    senders_cursor
-                        .walk_range(tx_range.clone())?
-                        .collect::<Result<HashMap<_, _>, _>>()?;
-
-                let mut senders = Vec::with_capacity(body.len());
-                for (tx_num, tx) in tx_range.zip(body.iter()) {
-                    match known_senders.get(&tx_num) {
-                        None => {
-                            // recover the sender from the transaction if not found
-                            let sender = tx
-                                .recover_signer_unchecked()
-                                .ok_or_else(|| ProviderError::SenderRecoveryError)?;
-                            senders.push(sender);
-                        }
-                        Some(sender) => senders.push(*sender),
-                    }
-                }
+                self.transactions_with_numbers_with_cursor(tx_range, &mut tx_cursor)?
+            };
+            let tx_count = block_transactions.len();
+            transactions.extend(block_transactions);
+            Ok((header, ommers, withdrawals, tx_count))
+        })?;
+
+        let mut senders = self
+            .senders_of_transactions(
+                &transactions,
+                &mut self.tx.cursor_read::<tables::TransactionSenders>()?,
+                true,
+            )?
+            .into_iter();
+        let mut transactions = transactions.into_iter();
 
-                (body, senders)
+        let mut blocks_with_senders = Vec::with_capacity(blocks.len());
+        for (header, ommers, withdrawals, tx_count) in blocks {
+            let body = transactions
+                .by_ref()
+                .take(tx_count)
+                .map(|(_, tx)| tx.into())
+                .collect::<Vec<TransactionSigned>>();
+            let block_senders = senders.by_ref().take(tx_count).collect::<Vec<_>>();
+
+            // Blocks with senders that failed to recover are skipped.
+            let Some(block_senders) = block_senders.into_iter().collect::<Option<Vec<_>>>() else {
+                continue
             };
+            let block = Block { header, body, ommers, withdrawals };
+            if let Ok(block) = block.try_with_senders_unchecked(block_senders) {
+                blocks_with_senders.push(block);
+            }
+        }
 
-            Block { header, body, ommers, withdrawals }
-                .try_with_senders_unchecked(senders)
-                .map_err(|_| ProviderError::SenderRecoveryError)
-        })
+        Ok(blocks_with_senders)
     }
 }
 
@@ -1594,6 +3902,34 @@ impl<TX: DbTx> TransactionsProviderExt for DatabaseProvider<TX> {
             |_| true,
         )
     }
+
+    /// Recovers transaction senders by walking through `Transactions` table and recovering them
+    /// in a parallel manner. Returned unsorted.
+    fn transaction_senders_by_range(
+        &self,
+        tx_range: Range<TxNumber>,
+    ) -> ProviderResult<Vec<(Address, TxNumber)>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::Transactions,
+            tx_range,
+            |static_file, range, _| static_file.transaction_senders_by_range(range),
+            |tx_range, _| {
+                self.tx
+                    .cursor_read::<tables::Transactions>()?
+                    .walk_range(tx_range)?
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_par_iter()
+                    .map_init(Vec::new, |rlp_buf, (tx_num, transaction)| {
+                        transaction
+                            .encode_and_recover_unchecked(rlp_buf)
+                            .map(|sender| (sender, tx_num))
+                            .ok_or(ProviderError::TransactionSenderRecoveryError(tx_num))
+                    })
+                    .collect()
+            },
+            |_| true,
+        )
+    }
 }
 
 /// Calculates the hash of the given transaction
@@ -1624,6 +3960,29 @@ impl<TX: DbTx> TransactionsProvider for DatabaseProvider<TX> {
         )
     }
 
+    /// Reads the transactions in ascending order of their ids, reusing a single database cursor.
+    fn transactions_by_ids(
+        &self,
+        ids: impl IntoIterator<Item = TxNumber>,
+    ) -> ProviderResult<Vec<Option<TransactionSignedNoHash>>> {
+        let mut ids = ids.into_iter().enumerate().collect::<Vec<_>>();
+        ids.sort_unstable_by_key(|(_, id)| *id);
+
+        let static_file_upper_bound =
+            self.static_file_provider.get_highest_static_file_tx(StaticFileSegment::Transactions);
+        let mut cursor = self.tx.cursor_read::<tables::Transactions>()?;
+
+        let mut transactions = vec![None; ids.len()];
+        for (index, id) in ids {
+            transactions[index] = if static_file_upper_bound.map_or(false, |bound| bound >= id) {
+                self.static_file_provider.transaction_by_id_no_hash(id)?
+            } else {
+                cursor.seek_exact(id)?.map(|(_, transaction)| transaction)
+            };
+        }
+        Ok(transactions)
+    }
+
     fn transaction_by_hash(&self, hash: TxHash) -> ProviderResult<Option<TransactionSigned>> {
         if let Some(id) = self.transaction_id(hash)? {
             Ok(self.transaction_by_id_no_hash(id)?.map(|tx| TransactionSigned {
@@ -1781,6 +4140,8 @@ impl<TX: DbTx> ReceiptProvider for DatabaseProvider<TX> {
                 return if tx_range.is_empty() {
                     Ok(Some(Vec::new()))
                 } else {
+                    // The block's receipts may be split between static files and the database
+                    // during migration, `receipts_by_tx_range` reads both parts in order.
                     self.receipts_by_tx_range(tx_range).map(Some)
                 }
             }
@@ -1800,6 +4161,11 @@ impl<TX: DbTx> ReceiptProvider for DatabaseProvider<TX> {
             |_| true,
         )
     }
+
+    fn total_receipts_count(&self) -> ProviderResult<u64> {
+        // Static file receipts are included, if the static file provider supports them.
+        Ok(self.count_entries::<tables::Receipts>()? as u64)
+    }
 }
 
 impl<TX: DbTx> WithdrawalsProvider for DatabaseProvider<TX> {
@@ -1944,6 +4310,18 @@ impl<TX: DbTx> StageCheckpointReader for DatabaseProvider<TX> {
     fn get_stage_checkpoint_progress(&self, id: StageId) -> ProviderResult<Option<Vec<u8>>> {
         Ok(self.tx.get::<tables::StageCheckpointProgresses>(id.to_string())?)
     }
+
+    fn get_all_stage_checkpoints(&self) -> ProviderResult<Vec<(StageId, StageCheckpoint)>> {
+        let mut checkpoints = Vec::new();
+        for entry in self.tx.cursor_read::<tables::StageCheckpoints>()?.walk(None)? {
+            let (name, checkpoint) = entry?;
+            // Custom stages can't be mapped back to a `StageId`, so they're skipped.
+            if let Some(id) = StageId::ALL.into_iter().find(|id| id.as_str() == name) {
+                checkpoints.push((id, checkpoint));
+            }
+        }
+        Ok(checkpoints)
+    }
 }
 
 impl<TX: DbTxMut> StageCheckpointWriter for DatabaseProvider<TX> {
@@ -2015,39 +4393,62 @@ impl<TX: DbTx> StorageReader for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
-        self.tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(BlockNumberAddress::range(range))?
-            // fold all storages and save its old state so we can remove it from HashedStorage
-            // it is needed as it is dup table.
-            .try_fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
-                let (BlockNumberAddress((_, address)), storage_entry) = entry?;
+        let mut accounts: BTreeMap<Address, BTreeSet<B256>> = BTreeMap::new();
+        self.for_each_storage_change(
+            BlockNumberAddress::range(range),
+            |BlockNumberAddress((_, address)), storage_entry| {
                 accounts.entry(address).or_default().insert(storage_entry.key);
-                Ok(accounts)
-            })
+            },
+        )?;
+        Ok(accounts)
+    }
+
+    fn changed_storage_slots_for_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<B256>> {
+        let mut cursor = self.tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+        let mut slots = BTreeSet::new();
+        // Changesets are keyed by block number and address, so seek directly to the entries of the
+        // address in each block instead of walking the changesets of all addresses.
+        for block_number in range {
+            let Some((_, storage_entry)) =
+                cursor.seek_exact(BlockNumberAddress((block_number, address)))?
+            else {
+                continue
+            };
+            slots.insert(storage_entry.key);
+            while let Some((_, storage_entry)) = cursor.next_dup()? {
+                slots.insert(storage_entry.key);
+            }
+        }
+        Ok(slots)
     }
 
     fn changed_storages_and_blocks_with_range(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-
-        let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
-                BTreeMap::new(),
-                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
-                    let (index, storage) = entry?;
-                    storages
-                        .entry((index.address(), storage.key))
-                        .or_default()
-                        .push(index.block_number());
-                    Ok(storages)
-                },
-            )?;
+        let mut storage_changeset_lists: BTreeMap<(Address, B256), Vec<u64>> = BTreeMap::new();
+        self.for_each_storage_transition(range, |address, key, block_number| {
+            storage_changeset_lists.entry((address, key)).or_default().push(block_number);
+        })?;
 
         Ok(storage_changeset_lists)
     }
+
+    fn for_each_storage_transition(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        mut f: impl FnMut(Address, B256, BlockNumber),
+    ) -> ProviderResult<()> {
+        // The changeset table is keyed by `(block_number, address)`, so walking it visits blocks in
+        // ascending order, which keeps the per-key block lists sorted.
+        self.for_each_storage_change(BlockNumberAddress::range(range), |index, storage| {
+            f(index.address(), storage.key, index.block_number())
+        })
+    }
 }
 
 impl<TX: DbTxMut + DbTx> HashingWriter for DatabaseProvider<TX> {
@@ -2058,15 +4459,11 @@ impl<TX: DbTxMut + DbTx> HashingWriter for DatabaseProvider<TX> {
         // Aggregate all block changesets and make a list of accounts that have been changed.
         // Note that collecting and then reversing the order is necessary to ensure that the
         // changes are applied in the correct order.
-        let hashed_accounts = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| entry.map(|(_, e)| (keccak256(e.address), e.info)))
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .rev()
-            .collect::<BTreeMap<_, _>>();
+        let mut changes = Vec::new();
+        self.for_each_account_change(range, |_, account_before| {
+            changes.push((keccak256(account_before.address), account_before.info))
+        })?;
+        let hashed_accounts = changes.into_iter().rev().collect::<BTreeMap<_, _>>();
 
         // Apply values to HashedState, and remove the account if it's None.
         let mut hashed_accounts_cursor = self.tx.cursor_write::<tables::HashedAccounts>()?;
@@ -2103,15 +4500,14 @@ impl<TX: DbTxMut + DbTx> HashingWriter for DatabaseProvider<TX> {
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<HashMap<B256, BTreeSet<B256>>> {
         // Aggregate all block changesets and make list of accounts that have been changed.
-        let mut changesets = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-        let mut hashed_storages = changesets
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(BlockNumberAddress((_, address)), storage_entry)| {
-                    (keccak256(address), keccak256(storage_entry.key), storage_entry.value)
-                })
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let mut hashed_storages = Vec::new();
+        self.for_each_storage_change(range, |BlockNumberAddress((_, address)), storage_entry| {
+            hashed_storages.push((
+                keccak256(address),
+                keccak256(storage_entry.key),
+                storage_entry.value,
+            ))
+        })?;
         hashed_storages.sort_by_key(|(ha, hk, _)| (*ha, *hk));
 
         // Apply values to HashedState, and remove the account if it's None.
@@ -2253,6 +4649,10 @@ impl<TX: DbTxMut + DbTx> HashingWriter for DatabaseProvider<TX> {
 
         Ok(())
     }
+
+    fn storage_root_for_account(&self, hashed_address: B256) -> ProviderResult<B256> {
+        self.hashed_storage_root(hashed_address)
+    }
 }
 
 impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
@@ -2260,12 +4660,10 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<usize> {
-        let mut last_indices = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| entry.map(|(index, account)| (account.address, index)))
-            .collect::<Result<Vec<_>, _>>()?;
+        let mut last_indices = Vec::new();
+        self.for_each_account_change(range, |index, account| {
+            last_indices.push((account.address, index))
+        })?;
         last_indices.sort_by_key(|(a, _)| *a);
 
         // Unwind the account history index.
@@ -2306,14 +4704,10 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         &self,
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<usize> {
-        let mut storage_changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(BlockNumberAddress((bn, address)), storage)| (address, storage.key, bn))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let mut storage_changesets = Vec::new();
+        self.for_each_storage_change(range, |BlockNumberAddress((bn, address)), storage| {
+            storage_changesets.push((address, storage.key, bn))
+        })?;
         storage_changesets.sort_by_key(|(address, key, _)| (*address, *key));
 
         let mut cursor = self.tx.cursor_write::<tables::StoragesHistory>()?;
@@ -2432,9 +4826,7 @@ impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
             // state root should be always correct as we are reverting state.
             // but for sake of double verification we will check it again.
             if new_state_root != parent_state_root {
-                let parent_hash = self
-                    .block_hash(parent_number)?
-                    .ok_or_else(|| ProviderError::HeaderNotFound(parent_number.into()))?;
+                let parent_hash = self.canonical_hash_checked(parent_number)?;
                 return Err(ProviderError::UnwindStateRootMismatch(Box::new(RootMismatch {
                     root: GotExpected { got: new_state_root, expected: parent_state_root },
                     block_number: parent_number,
@@ -2472,133 +4864,73 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
         block: SealedBlockWithSenders,
         prune_modes: Option<&PruneModes>,
     ) -> ProviderResult<StoredBlockBodyIndices> {
-        let block_number = block.number;
-
-        let mut durations_recorder = metrics::DurationsRecorder::default();
-
-        self.tx.put::<tables::CanonicalHeaders>(block_number, block.hash())?;
-        durations_recorder.record_relative(metrics::Action::InsertCanonicalHeaders);
+        self.insert_block_with_hashes(block, prune_modes).map(|(block_indices, _)| block_indices)
+    }
 
-        // Put header with canonical hashes.
-        self.tx.put::<tables::Headers>(block_number, block.header.as_ref().clone())?;
-        durations_recorder.record_relative(metrics::Action::InsertHeaders);
+    fn remove_block(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        let tip = self.last_block_number()?;
+        if block_number != tip {
+            return Err(ProviderError::NotTipBlock(block_number, tip))
+        }
+        if self.get_take_block_range::<true>(block_number..=block_number)?.is_empty() {
+            return Err(ProviderError::HeaderNotFound(block_number.into()))
+        }
+        self.tx.delete::<tables::BlockBodyIndices>(block_number, None)?;
+        Ok(())
+    }
 
-        self.tx.put::<tables::HeaderNumbers>(block.hash(), block_number)?;
-        durations_recorder.record_relative(metrics::Action::InsertHeaderNumbers);
+    fn append_receipts(
+        &self,
+        first_tx_num: TxNumber,
+        receipts: Vec<Vec<Receipt>>,
+    ) -> ProviderResult<()> {
+        if receipts.is_empty() {
+            return Ok(())
+        }
 
-        // total difficulty
-        let ttd = if block_number == 0 {
-            block.difficulty
-        } else {
-            let parent_block_number = block_number - 1;
-            let parent_ttd = self.header_td_by_number(parent_block_number)?.unwrap_or_default();
-            durations_recorder.record_relative(metrics::Action::GetParentTD);
-            parent_ttd + block.difficulty
+        let check_next = |expected: TxNumber| {
+            if first_tx_num != expected {
+                return Err(ProviderError::UnexpectedReceiptNumber(GotExpected {
+                    got: first_tx_num,
+                    expected,
+                }))
+            }
+            Ok(())
         };
 
-        self.tx.put::<tables::HeaderTerminalDifficulties>(block_number, ttd.into())?;
-        durations_recorder.record_relative(metrics::Action::InsertHeaderTerminalDifficulties);
-
-        // insert body ommers data
-        if !block.ommers.is_empty() {
-            self.tx.put::<tables::BlockOmmers>(
-                block_number,
-                StoredBlockOmmers { ommers: block.block.ommers },
+        if let Some(last_block) =
+            self.static_file_provider.get_highest_static_file_block(StaticFileSegment::Receipts)
+        {
+            check_next(
+                self.static_file_provider
+                    .get_highest_static_file_tx(StaticFileSegment::Receipts)
+                    .map_or(0, |tx_num| tx_num + 1),
             )?;
-            durations_recorder.record_relative(metrics::Action::InsertBlockOmmers);
-        }
-
-        let mut next_tx_num = self
-            .tx
-            .cursor_read::<tables::TransactionBlocks>()?
-            .last()?
-            .map(|(n, _)| n + 1)
-            .unwrap_or_default();
-        durations_recorder.record_relative(metrics::Action::GetNextTxNum);
-        let first_tx_num = next_tx_num;
-
-        let tx_count = block.block.body.len() as u64;
-
-        // Ensures we have all the senders for the block's transactions.
-        let mut tx_senders_elapsed = Duration::default();
-        let mut transactions_elapsed = Duration::default();
-        let mut tx_hash_numbers_elapsed = Duration::default();
-
-        for (transaction, sender) in block.block.body.into_iter().zip(block.senders.iter()) {
-            let hash = transaction.hash();
-
-            if prune_modes
-                .and_then(|modes| modes.sender_recovery)
-                .filter(|prune_mode| prune_mode.is_full())
-                .is_none()
-            {
-                let start = Instant::now();
-                self.tx.put::<tables::TransactionSenders>(next_tx_num, *sender)?;
-                tx_senders_elapsed += start.elapsed();
-            }
 
-            let start = Instant::now();
-            self.tx.put::<tables::Transactions>(next_tx_num, transaction.into())?;
-            let elapsed = start.elapsed();
-            if elapsed > Duration::from_secs(1) {
-                warn!(
-                    target: "providers::db",
-                    ?block_number,
-                    tx_num = %next_tx_num,
-                    hash = %hash,
-                    ?elapsed,
-                    "Transaction insertion took too long"
-                );
+            let mut writer =
+                self.static_file_provider.latest_writer(StaticFileSegment::Receipts)?;
+            let mut tx_num = first_tx_num;
+            for (block_number, block_receipts) in (last_block + 1..).zip(receipts) {
+                // The block has to be incremented even if it has no receipts, so that the block
+                // range of the static file stays consistent.
+                writer.increment_block(StaticFileSegment::Receipts, block_number)?;
+                for receipt in block_receipts {
+                    writer.append_receipt(tx_num, receipt)?;
+                    tx_num += 1;
+                }
             }
-            transactions_elapsed += elapsed;
-
-            if prune_modes
-                .and_then(|modes| modes.transaction_lookup)
-                .filter(|prune_mode| prune_mode.is_full())
-                .is_none()
-            {
-                let start = Instant::now();
-                self.tx.put::<tables::TransactionHashNumbers>(hash, next_tx_num)?;
-                tx_hash_numbers_elapsed += start.elapsed();
+        } else {
+            let mut cursor = self.tx.cursor_write::<tables::Receipts>()?;
+            if let Some((last_tx_num, _)) = cursor.last()? {
+                check_next(last_tx_num + 1)?;
             }
-            next_tx_num += 1;
-        }
-        durations_recorder
-            .record_duration(metrics::Action::InsertTransactionSenders, tx_senders_elapsed);
-        durations_recorder
-            .record_duration(metrics::Action::InsertTransactions, transactions_elapsed);
-        durations_recorder.record_duration(
-            metrics::Action::InsertTransactionHashNumbers,
-            tx_hash_numbers_elapsed,
-        );
 
-        if let Some(withdrawals) = block.block.withdrawals {
-            if !withdrawals.is_empty() {
-                self.tx.put::<tables::BlockWithdrawals>(
-                    block_number,
-                    StoredBlockWithdrawals { withdrawals },
-                )?;
-                durations_recorder.record_relative(metrics::Action::InsertBlockWithdrawals);
+            for (tx_num, receipt) in (first_tx_num..).zip(receipts.into_iter().flatten()) {
+                cursor.append(tx_num, receipt)?;
             }
         }
 
-        let block_indices = StoredBlockBodyIndices { first_tx_num, tx_count };
-        self.tx.put::<tables::BlockBodyIndices>(block_number, block_indices.clone())?;
-        durations_recorder.record_relative(metrics::Action::InsertBlockBodyIndices);
-
-        if !block_indices.is_empty() {
-            self.tx.put::<tables::TransactionBlocks>(block_indices.last_tx_num(), block_number)?;
-            durations_recorder.record_relative(metrics::Action::InsertTransactionBlocks);
-        }
-
-        debug!(
-            target: "providers::db",
-            ?block_number,
-            actions = ?durations_recorder.actions,
-            "Inserted block"
-        );
-
-        Ok(block_indices)
+        Ok(())
     }
 
     fn append_blocks_with_state(
@@ -2609,28 +4941,13 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
         trie_updates: TrieUpdates,
         prune_modes: Option<&PruneModes>,
     ) -> ProviderResult<()> {
-        if blocks.is_empty() {
-            debug!(target: "providers::db", "Attempted to append empty block range");
-            return Ok(())
-        }
-
-        let first_number = blocks.first().unwrap().number;
-
-        let last = blocks.last().unwrap();
-        let last_block_number = last.number;
-
         let mut durations_recorder = metrics::DurationsRecorder::default();
 
-        // Insert the blocks
-        for block in blocks {
-            self.insert_block(block, prune_modes)?;
-            durations_recorder.record_relative(metrics::Action::InsertBlock);
-        }
-
-        // Write state and changesets to the database.
-        // Must be written after blocks because of the receipt lookup.
-        state.write_to_storage(self.tx_ref(), None, OriginalValuesKnown::No)?;
-        durations_recorder.record_relative(metrics::Action::InsertState);
+        let Some(range) =
+            self.append_blocks_and_state(blocks, state, prune_modes, &mut durations_recorder)?
+        else {
+            return Ok(())
+        };
 
         // insert hashes and intermediate merkle nodes
         {
@@ -2639,14 +4956,11 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
         }
         durations_recorder.record_relative(metrics::Action::InsertHashes);
 
-        self.update_history_indices(first_number..=last_block_number)?;
-        durations_recorder.record_relative(metrics::Action::InsertHistoryIndices);
-
         // Update pipeline progress
-        self.update_pipeline_stages(last_block_number, false)?;
+        self.update_pipeline_stages(*range.end(), false)?;
         durations_recorder.record_relative(metrics::Action::UpdatePipelineStages);
 
-        debug!(target: "providers::db", range = ?first_number..=last_block_number, actions = ?durations_recorder.actions, "Appended blocks");
+        debug!(target: "providers::db", ?range, actions = ?durations_recorder.actions, "Appended blocks");
 
         Ok(())
     }
@@ -2659,6 +4973,14 @@ impl<TX: DbTx> PruneCheckpointReader for DatabaseProvider<TX> {
     ) -> ProviderResult<Option<PruneCheckpoint>> {
         Ok(self.tx.get::<tables::PruneCheckpoints>(segment)?)
     }
+
+    fn get_all_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
+        Ok(self
+            .tx
+            .cursor_read::<tables::PruneCheckpoints>()?
+            .walk(None)?
+            .collect::<Result<_, _>>()?)
+    }
 }
 
 impl<TX: DbTxMut> PruneCheckpointWriter for DatabaseProvider<TX> {
@@ -2684,6 +5006,16 @@ impl<TX: DbTx> StatsReader for DatabaseProvider<TX> {
     }
 }
 
+/// Returns the range of transaction numbers spanning from the first to the last block body, or
+/// `None` if all blocks in between are empty.
+fn block_bodies_tx_range(
+    first: &StoredBlockBodyIndices,
+    last: &StoredBlockBodyIndices,
+) -> Option<Range<TxNumber>> {
+    let range = first.first_tx_num()..last.next_tx_num();
+    (!range.is_empty()).then_some(range)
+}
+
 fn range_size_hint(range: &impl RangeBounds<TxNumber>) -> Option<usize> {
     let start = match range.start_bound().cloned() {
         Bound::Included(start) => start,
@@ -170,6 +170,10 @@ where
         self.database.sealed_header(number)
     }
 
+    fn latest_sealed_header(&self) -> ProviderResult<Option<SealedHeader>> {
+        self.database.latest_sealed_header()
+    }
+
     fn sealed_headers_range(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -264,6 +268,28 @@ where
         Ok(block)
     }
 
+    fn find_sealed_block_by_hash(
+        &self,
+        hash: B256,
+        source: BlockSource,
+    ) -> ProviderResult<Option<SealedBlock>> {
+        let block = match source {
+            BlockSource::Any => {
+                // check database first
+                match self.database.find_sealed_block_by_hash(hash, BlockSource::Database)? {
+                    Some(block) => Some(block),
+                    None => self.tree.block_by_hash(hash),
+                }
+            }
+            BlockSource::Pending => self.tree.block_by_hash(hash),
+            BlockSource::Database => {
+                self.database.find_sealed_block_by_hash(hash, BlockSource::Database)?
+            }
+        };
+
+        Ok(block)
+    }
+
     fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Block>> {
         match id {
             BlockHashOrNumber::Hash(hash) => self.find_block_by_hash(hash, BlockSource::Any),
@@ -287,6 +313,13 @@ where
         self.database.ommers(id)
     }
 
+    fn ommers_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Header>)>> {
+        self.database.ommers_range(range)
+    }
+
     fn block_body_indices(
         &self,
         number: BlockNumber,
@@ -294,6 +327,13 @@ where
         self.database.block_body_indices(number)
     }
 
+    fn block_body_indices_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, StoredBlockBodyIndices)>> {
+        self.database.block_body_indices_range(range)
+    }
+
     /// Returns the block with senders with matching number or hash from database.
     ///
     /// **NOTE: If [TransactionVariant::NoHash] is provided then the transactions have invalid
@@ -308,6 +348,14 @@ where
         self.database.block_with_senders(id, transaction_kind)
     }
 
+    fn sealed_block_with_senders(
+        &self,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        self.database.sealed_block_with_senders(id, transaction_kind)
+    }
+
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
         self.database.block_range(range)
     }
@@ -339,6 +387,13 @@ where
         self.database.transaction_by_id_no_hash(id)
     }
 
+    fn transactions_by_ids(
+        &self,
+        ids: impl IntoIterator<Item = TxNumber>,
+    ) -> ProviderResult<Vec<Option<TransactionSignedNoHash>>> {
+        self.database.transactions_by_ids(ids)
+    }
+
     fn transaction_by_hash(&self, hash: TxHash) -> ProviderResult<Option<TransactionSigned>> {
         self.database.transaction_by_hash(hash)
     }
@@ -409,6 +464,10 @@ where
     ) -> ProviderResult<Vec<Receipt>> {
         self.database.receipts_by_tx_range(range)
     }
+
+    fn total_receipts_count(&self) -> ProviderResult<u64> {
+        self.database.total_receipts_count()
+    }
 }
 
 impl<DB> ReceiptProviderIdExt for BlockchainProvider<DB>
@@ -466,6 +525,10 @@ where
     fn get_stage_checkpoint_progress(&self, id: StageId) -> ProviderResult<Option<Vec<u8>>> {
         self.database.provider()?.get_stage_checkpoint_progress(id)
     }
+
+    fn get_all_stage_checkpoints(&self) -> ProviderResult<Vec<(StageId, StageCheckpoint)>> {
+        self.database.provider()?.get_all_stage_checkpoints()
+    }
 }
 
 impl<DB> EvmEnvProvider for BlockchainProvider<DB>
@@ -549,6 +612,10 @@ where
     ) -> ProviderResult<Option<PruneCheckpoint>> {
         self.database.provider()?.get_prune_checkpoint(segment)
     }
+
+    fn get_all_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
+        self.database.provider()?.get_all_prune_checkpoints()
+    }
 }
 
 impl<DB> ChainSpecProvider for BlockchainProvider<DB>
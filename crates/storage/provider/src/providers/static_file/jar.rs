@@ -122,6 +122,11 @@ impl<'a> HeaderProvider for StaticFileJarProvider<'a> {
             .map(|(header, hash)| header.seal(hash)))
     }
 
+    fn latest_sealed_header(&self) -> ProviderResult<Option<SealedHeader>> {
+        // Information on live database
+        Err(ProviderError::UnsupportedProvider)
+    }
+
     fn sealed_headers_while(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -325,4 +330,9 @@ impl<'a> ReceiptProvider for StaticFileJarProvider<'a> {
         }
         Ok(receipts)
     }
+
+    fn total_receipts_count(&self) -> ProviderResult<u64> {
+        // Receipts span multiple jars, counted by the static file provider
+        Err(ProviderError::UnsupportedProvider)
+    }
 }
@@ -9,6 +9,7 @@ use crate::{
 };
 use dashmap::{mapref::entry::Entry as DashMapEntry, DashMap};
 use parking_lot::RwLock;
+use rayon::prelude::*;
 use reth_db::{
     codecs::CompactU256,
     models::StoredBlockBodyIndices,
@@ -566,6 +567,23 @@ impl StaticFileProvider {
         Ok(result)
     }
 
+    /// Fetches headers within a specified range paired with their stored total difficulty.
+    pub fn headers_with_td_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(Header, U256)>> {
+        self.fetch_range_with_predicate(
+            StaticFileSegment::Headers,
+            to_range(range),
+            |cursor, number| {
+                Ok(cursor
+                    .get_two::<HeaderMask<Header, CompactU256>>(number.into())?
+                    .map(|(header, td)| (header, td.into())))
+            },
+            |_| true,
+        )
+    }
+
     /// Fetches data within a specified range across multiple static files.
     ///
     /// Returns an iterator over the data
@@ -692,6 +710,21 @@ impl StaticFileProvider {
         Ok(data)
     }
 
+    /// Returns the headers in the given range while `predicate` returns `true`, without reading
+    /// or sealing them with their hashes.
+    pub fn headers_while(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        predicate: impl FnMut(&Header) -> bool,
+    ) -> ProviderResult<Vec<Header>> {
+        self.fetch_range_with_predicate(
+            StaticFileSegment::Headers,
+            to_range(range),
+            |cursor, number| cursor.get_one::<HeaderMask<Header>>(number.into()),
+            predicate,
+        )
+    }
+
     #[cfg(any(test, feature = "test-utils"))]
     /// Returns static_files directory
     pub fn path(&self) -> &Path {
@@ -803,6 +836,11 @@ impl HeaderProvider for StaticFileProvider {
             .sealed_header(num)
     }
 
+    fn latest_sealed_header(&self) -> ProviderResult<Option<SealedHeader>> {
+        self.get_highest_static_file_block(StaticFileSegment::Headers)
+            .map_or(Ok(None), |num| self.sealed_header(num))
+    }
+
     fn sealed_headers_while(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -868,6 +906,10 @@ impl ReceiptProvider for StaticFileProvider {
             |_| true,
         )
     }
+
+    fn total_receipts_count(&self) -> ProviderResult<u64> {
+        Ok(self.count_entries::<tables::Receipts>()? as u64)
+    }
 }
 
 impl TransactionsProviderExt for StaticFileProvider {
@@ -928,6 +970,30 @@ impl TransactionsProviderExt for StaticFileProvider {
 
         Ok(tx_list)
     }
+
+    fn transaction_senders_by_range(
+        &self,
+        tx_range: Range<TxNumber>,
+    ) -> ProviderResult<Vec<(Address, TxNumber)>> {
+        self.fetch_range_with_predicate(
+            StaticFileSegment::Transactions,
+            tx_range,
+            |cursor, number| {
+                Ok(cursor
+                    .get_one::<TransactionMask<TransactionSignedNoHash>>(number.into())?
+                    .map(|transaction| (number, transaction)))
+            },
+            |_| true,
+        )?
+        .into_par_iter()
+        .map_init(Vec::new, |rlp_buf, (tx_num, transaction)| {
+            transaction
+                .encode_and_recover_unchecked(rlp_buf)
+                .map(|sender| (sender, tx_num))
+                .ok_or(ProviderError::TransactionSenderRecoveryError(tx_num))
+        })
+        .collect()
+    }
 }
 
 impl TransactionsProvider for StaticFileProvider {
@@ -1085,11 +1151,27 @@ impl BlockReader for StaticFileProvider {
         Err(ProviderError::UnsupportedProvider)
     }
 
+    fn ommers_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Header>)>> {
+        // Required data not present in static_files
+        Err(ProviderError::UnsupportedProvider)
+    }
+
     fn block_body_indices(&self, _num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
         // Required data not present in static_files
         Err(ProviderError::UnsupportedProvider)
     }
 
+    fn block_body_indices_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, StoredBlockBodyIndices)>> {
+        // Required data not present in static_files
+        Err(ProviderError::UnsupportedProvider)
+    }
+
     fn block_with_senders(
         &self,
         _id: BlockHashOrNumber,
@@ -1099,6 +1181,15 @@ impl BlockReader for StaticFileProvider {
         Err(ProviderError::UnsupportedProvider)
     }
 
+    fn sealed_block_with_senders(
+        &self,
+        _id: BlockHashOrNumber,
+        _transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        // Required data not present in static_files
+        Err(ProviderError::UnsupportedProvider)
+    }
+
     fn block_range(&self, _range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
         // Required data not present in static_files
         Err(ProviderError::UnsupportedProvider)
@@ -171,6 +171,11 @@ impl HeaderProvider for MockEthProvider {
         Ok(self.header_by_number(number)?.map(|h| h.seal_slow()))
     }
 
+    fn latest_sealed_header(&self) -> ProviderResult<Option<SealedHeader>> {
+        let lock = self.headers.lock();
+        Ok(lock.values().max_by_key(|h| h.number).cloned().map(|h| h.seal_slow()))
+    }
+
     fn sealed_headers_while(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -356,6 +361,10 @@ impl ReceiptProvider for MockEthProvider {
     ) -> ProviderResult<Vec<Receipt>> {
         Ok(vec![])
     }
+
+    fn total_receipts_count(&self) -> ProviderResult<u64> {
+        Ok(0)
+    }
 }
 
 impl ReceiptProviderIdExt for MockEthProvider {}
@@ -462,10 +471,24 @@ impl BlockReader for MockEthProvider {
         Ok(None)
     }
 
+    fn ommers_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Header>)>> {
+        Ok(vec![])
+    }
+
     fn block_body_indices(&self, _num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
         Ok(None)
     }
 
+    fn block_body_indices_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, StoredBlockBodyIndices)>> {
+        Ok(vec![])
+    }
+
     fn block_with_senders(
         &self,
         _id: BlockHashOrNumber,
@@ -474,6 +497,14 @@ impl BlockReader for MockEthProvider {
         Ok(None)
     }
 
+    fn sealed_block_with_senders(
+        &self,
+        _id: BlockHashOrNumber,
+        _transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        Ok(None)
+    }
+
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
         let lock = self.blocks.lock();
 
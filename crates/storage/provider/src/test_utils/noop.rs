@@ -101,10 +101,24 @@ impl BlockReader for NoopProvider {
         Ok(None)
     }
 
+    fn ommers_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Header>)>> {
+        Ok(vec![])
+    }
+
     fn block_body_indices(&self, _num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
         Ok(None)
     }
 
+    fn block_body_indices_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, StoredBlockBodyIndices)>> {
+        Ok(vec![])
+    }
+
     fn block_with_senders(
         &self,
         _id: BlockHashOrNumber,
@@ -113,6 +127,14 @@ impl BlockReader for NoopProvider {
         Ok(None)
     }
 
+    fn sealed_block_with_senders(
+        &self,
+        _id: BlockHashOrNumber,
+        _transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        Ok(None)
+    }
+
     fn block_range(&self, _range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
         Ok(vec![])
     }
@@ -240,6 +262,10 @@ impl ReceiptProvider for NoopProvider {
     ) -> ProviderResult<Vec<Receipt>> {
         Ok(vec![])
     }
+
+    fn total_receipts_count(&self) -> ProviderResult<u64> {
+        Ok(0)
+    }
 }
 
 impl ReceiptProviderIdExt for NoopProvider {}
@@ -269,6 +295,10 @@ impl HeaderProvider for NoopProvider {
         Ok(None)
     }
 
+    fn latest_sealed_header(&self) -> ProviderResult<Option<SealedHeader>> {
+        Ok(None)
+    }
+
     fn sealed_headers_while(
         &self,
         _range: impl RangeBounds<BlockNumber>,
@@ -433,6 +463,10 @@ impl StageCheckpointReader for NoopProvider {
     fn get_stage_checkpoint_progress(&self, _id: StageId) -> ProviderResult<Option<Vec<u8>>> {
         Ok(None)
     }
+
+    fn get_all_stage_checkpoints(&self) -> ProviderResult<Vec<(StageId, StageCheckpoint)>> {
+        Ok(vec![])
+    }
 }
 
 impl WithdrawalsProvider for NoopProvider {
@@ -455,4 +489,8 @@ impl PruneCheckpointReader for NoopProvider {
     ) -> ProviderResult<Option<PruneCheckpoint>> {
         Ok(None)
     }
+
+    fn get_all_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
+        Ok(Vec::new())
+    }
 }
@@ -20,6 +20,9 @@ pub trait AccountReader: Send + Sync {
 #[auto_impl(&, Arc, Box)]
 pub trait AccountExtReader: Send + Sync {
     /// Iterate over account changesets and return all account address that were changed.
+    ///
+    /// All kinds of range bounds are respected precisely: an unbounded start iterates from the
+    /// first changeset and an unbounded end iterates up to the last one, i.e. the tip.
     fn changed_accounts_with_range(
         &self,
         _range: impl RangeBounds<BlockNumber>,
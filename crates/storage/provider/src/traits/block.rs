@@ -7,7 +7,7 @@ use reth_db::models::StoredBlockBodyIndices;
 use reth_interfaces::provider::ProviderResult;
 use reth_primitives::{
     Block, BlockHashOrNumber, BlockId, BlockNumber, BlockNumberOrTag, BlockWithSenders, Header,
-    PruneModes, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, B256,
+    PruneModes, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, TxNumber, B256,
 };
 use reth_trie::{updates::TrieUpdates, HashedPostState};
 use std::ops::RangeInclusive;
@@ -74,6 +74,19 @@ pub trait BlockReader:
     /// Returns `None` if block is not found.
     fn find_block_by_hash(&self, hash: B256, source: BlockSource) -> ProviderResult<Option<Block>>;
 
+    /// Tries to find in the given block source and returns the block sealed with the given hash.
+    ///
+    /// Since the hash is already known, the block doesn't need to be resealed.
+    ///
+    /// Returns `None` if block is not found.
+    fn find_sealed_block_by_hash(
+        &self,
+        hash: B256,
+        source: BlockSource,
+    ) -> ProviderResult<Option<SealedBlock>> {
+        Ok(self.find_block_by_hash(hash, source)?.map(|block| block.seal(hash)))
+    }
+
     /// Returns the block with given id from the database.
     ///
     /// Returns `None` if block is not found.
@@ -99,6 +112,18 @@ pub trait BlockReader:
     /// Returns `None` if block is not found.
     fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Header>>>;
 
+    /// Returns the ommers/uncle headers of all blocks in the given inclusive range, paired with
+    /// their block number.
+    ///
+    /// Blocks without ommers, including all blocks after the Paris (Merge) hardfork, yield an
+    /// empty vector.
+    ///
+    /// Note: returns only available blocks
+    fn ommers_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Vec<Header>)>>;
+
     /// Returns the block with matching hash from the database.
     ///
     /// Returns `None` if block is not found.
@@ -118,6 +143,15 @@ pub trait BlockReader:
     /// Returns `None` if block is not found.
     fn block_body_indices(&self, num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>>;
 
+    /// Returns the block body indices of all blocks in the given inclusive range, paired with
+    /// their block number.
+    ///
+    /// Note: returns only available block body indices
+    fn block_body_indices_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, StoredBlockBodyIndices)>>;
+
     /// Returns the block with senders with matching number or hash from database.
     ///
     /// Returns the block's transactions in the requested variant.
@@ -129,11 +163,39 @@ pub trait BlockReader:
         transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<BlockWithSenders>>;
 
+    /// Returns the sealed block with senders with matching number or hash from database.
+    ///
+    /// Unlike [BlockReader::block_with_senders], the block hash is taken from the stored sealed
+    /// header, so it doesn't need to be recomputed.
+    ///
+    /// Returns the block's transactions in the requested variant.
+    ///
+    /// Returns `None` if block is not found.
+    fn sealed_block_with_senders(
+        &self,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>>;
+
     /// Returns all blocks in the given inclusive range.
     ///
     /// Note: returns only available blocks
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>>;
 
+    /// Returns at most `max_count` blocks starting at block `start`.
+    ///
+    /// Note: returns only available blocks
+    fn block_range_limited(
+        &self,
+        start: BlockNumber,
+        max_count: u64,
+    ) -> ProviderResult<Vec<Block>> {
+        if max_count == 0 {
+            return Ok(Vec::new())
+        }
+        self.block_range(start..=start.saturating_add(max_count - 1))
+    }
+
     /// retrieves a range of blocks from the database, along with the senders of each
     /// transaction in the blocks.
     ///
@@ -302,6 +364,27 @@ pub trait BlockWriter: Send + Sync {
         prune_modes: Option<&PruneModes>,
     ) -> ProviderResult<StoredBlockBodyIndices>;
 
+    /// Remove the tip block from the database: its header, body, ommers, withdrawals,
+    /// transactions, senders, transaction lookup entries and body indices.
+    ///
+    /// State and changesets are not touched, use the execution range unwind for them.
+    ///
+    /// Returns an error if the block is not the tip, so that no gaps are left behind.
+    fn remove_block(&self, block_number: BlockNumber) -> ProviderResult<()>;
+
+    /// Appends receipts of consecutive blocks, starting at transaction number `first_tx_num`.
+    ///
+    /// Each inner vector holds the receipts of a single block. If receipts are already kept in
+    /// static files they are appended to the receipts static file, otherwise they are written to
+    /// the `Receipts` table. The static file writer is not committed, that is left to the caller.
+    ///
+    /// Returns an error if `first_tx_num` doesn't follow the last stored receipt.
+    fn append_receipts(
+        &self,
+        first_tx_num: TxNumber,
+        receipts: Vec<Vec<Receipt>>,
+    ) -> ProviderResult<()>;
+
     /// Appends a batch of sealed blocks to the blockchain, including sender information, and
     /// updates the post-state.
     ///
@@ -26,6 +26,10 @@ pub trait BlockHashReader: Send + Sync {
     /// Returns the available hashes of that range.
     ///
     /// Note: The range is `start..end`, so the expected result is `[start..end)`
+    ///
+    /// Unlike [`HeaderProvider::headers_range`](crate::HeaderProvider::headers_range), the end is
+    /// always exclusive. Callers pairing headers with their hashes must pass the same `start..end`
+    /// range to both.
     fn canonical_hashes_range(
         &self,
         start: BlockNumber,
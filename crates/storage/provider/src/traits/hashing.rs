@@ -62,4 +62,14 @@ pub trait HashingWriter: Send + Sync {
         end_block_hash: B256,
         expected_state_root: B256,
     ) -> ProviderResult<()>;
+
+    /// Computes the storage root of a single account from the
+    /// [reth_db::tables::HashedStorages] table.
+    ///
+    /// All storage slots of the account are marked as changed, so the root is recomputed from the
+    /// hashed storage instead of reusing intermediate nodes of the stored storage trie. The
+    /// account trie is not touched.
+    ///
+    /// Returns the empty trie root if the account has no storage.
+    fn storage_root_for_account(&self, hashed_address: B256) -> ProviderResult<B256>;
 }
@@ -18,6 +18,8 @@ pub trait HeaderProvider: Send + Sync {
     fn header_by_number(&self, num: u64) -> ProviderResult<Option<Header>>;
 
     /// Get header by block number or hash
+    ///
+    /// Returns `None` if the header is not found, e.g. for an unknown block hash.
     fn header_by_hash_or_number(
         &self,
         hash_or_num: BlockHashOrNumber,
@@ -40,6 +42,12 @@ pub trait HeaderProvider: Send + Sync {
     /// Get a single sealed header by block number.
     fn sealed_header(&self, number: BlockNumber) -> ProviderResult<Option<SealedHeader>>;
 
+    /// Get the sealed header of the highest stored block, as returned by
+    /// [crate::BlockNumReader::last_block_number].
+    ///
+    /// Returns `None` if no headers are stored.
+    fn latest_sealed_header(&self) -> ProviderResult<Option<SealedHeader>>;
+
     /// Get headers in range of block numbers.
     fn sealed_headers_range(
         &self,
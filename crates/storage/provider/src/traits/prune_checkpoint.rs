@@ -9,6 +9,11 @@ pub trait PruneCheckpointReader: Send + Sync {
         &self,
         segment: PruneSegment,
     ) -> ProviderResult<Option<PruneCheckpoint>>;
+
+    /// Fetch all the prune checkpoints.
+    ///
+    /// Segments without a checkpoint are not included.
+    fn get_all_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>>;
 }
 
 /// The trait for updating prune checkpoint related data.
@@ -28,6 +28,12 @@ pub trait ReceiptProvider: Send + Sync {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Receipt>>;
+
+    /// Get the total number of stored receipts.
+    ///
+    /// Receipts in both the database and static files are counted. Pruned receipts are not, so
+    /// the count is not necessarily the highest stored transaction number plus one.
+    fn total_receipts_count(&self) -> ProviderResult<u64>;
 }
 
 /// Trait extension for `ReceiptProvider`, for types that implement `BlockId` conversion.
@@ -12,6 +12,9 @@ pub trait StageCheckpointReader: Send + Sync {
 
     /// Get stage checkpoint progress.
     fn get_stage_checkpoint_progress(&self, id: StageId) -> ProviderResult<Option<Vec<u8>>>;
+
+    /// Fetch the checkpoints of all stages in [`StageId::ALL`] that have one.
+    fn get_all_stage_checkpoints(&self) -> ProviderResult<Vec<(StageId, StageCheckpoint)>>;
 }
 
 /// The trait for updating stage checkpoint related data.
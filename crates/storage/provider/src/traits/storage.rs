@@ -22,6 +22,16 @@ pub trait StorageReader: Send + Sync {
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>>;
 
+    /// Iterate over storage changesets and return all storage slots of the given address that were
+    /// changed.
+    ///
+    /// NOTE: Get inclusive range of blocks.
+    fn changed_storage_slots_for_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<B256>>;
+
     /// Iterate over storage changesets and return all storage slots that were changed alongside
     /// each specific set of blocks.
     ///
@@ -30,4 +40,18 @@ pub trait StorageReader: Send + Sync {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>>;
+
+    /// Iterate over storage changesets and call `f` with the address, storage key and block number
+    /// of every storage transition, without collecting them in memory.
+    ///
+    /// Transitions are yielded in changeset order, so for any given `(address, key)` pair the block
+    /// numbers are strictly ascending, while different pairs are interleaved. This is the ordering
+    /// required for appending history indices per key.
+    ///
+    /// NOTE: Get inclusive range of blocks.
+    fn for_each_storage_transition(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        f: impl FnMut(Address, B256, BlockNumber),
+    ) -> ProviderResult<()>;
 }
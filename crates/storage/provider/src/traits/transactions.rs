@@ -24,6 +24,17 @@ pub trait TransactionsProvider: BlockNumReader + Send + Sync {
         id: TxNumber,
     ) -> ProviderResult<Option<TransactionSignedNoHash>>;
 
+    /// Get transactions by their ids without computing the hashes.
+    ///
+    /// The ids don't need to be contiguous or sorted. Transactions are returned in the order of
+    /// the given ids, with `None` for the ones that are not found.
+    fn transactions_by_ids(
+        &self,
+        ids: impl IntoIterator<Item = TxNumber>,
+    ) -> ProviderResult<Vec<Option<TransactionSignedNoHash>>> {
+        ids.into_iter().map(|id| self.transaction_by_id_no_hash(id)).collect()
+    }
+
     /// Get transaction by transaction hash.
     fn transaction_by_hash(&self, hash: TxHash) -> ProviderResult<Option<TransactionSigned>>;
 
@@ -93,4 +104,10 @@ pub trait TransactionsProviderExt: BlockReader + Send + Sync {
         &self,
         tx_range: Range<TxNumber>,
     ) -> ProviderResult<Vec<(TxHash, TxNumber)>>;
+
+    /// Get transaction senders from a transaction range, recovering them from the transactions.
+    fn transaction_senders_by_range(
+        &self,
+        tx_range: Range<TxNumber>,
+    ) -> ProviderResult<Vec<(Address, TxNumber)>>;
 }
@@ -1,5 +1,5 @@
 use reth_interfaces::provider::ProviderResult;
-use reth_primitives::{BlockHashOrNumber, Withdrawal, Withdrawals};
+use reth_primitives::{proofs, BlockHashOrNumber, Withdrawal, Withdrawals, B256};
 
 ///  Client trait for fetching [Withdrawal] related data.
 #[auto_impl::auto_impl(&, Arc)]
@@ -11,6 +11,20 @@ pub trait WithdrawalsProvider: Send + Sync {
         timestamp: u64,
     ) -> ProviderResult<Option<Withdrawals>>;
 
+    /// Get the root of the withdrawals trie for the block.
+    ///
+    /// Returns `None` if the block is pre-Shanghai, an empty withdrawal list results in the root
+    /// of an empty trie.
+    fn withdrawals_root_by_block(
+        &self,
+        id: BlockHashOrNumber,
+        timestamp: u64,
+    ) -> ProviderResult<Option<B256>> {
+        Ok(self
+            .withdrawals_by_block(id, timestamp)?
+            .map(|withdrawals| proofs::calculate_withdrawals_root(&withdrawals)))
+    }
+
     /// Get latest withdrawal from this block or earlier .
     fn latest_withdrawal(&self) -> ProviderResult<Option<Withdrawal>>;
 }